@@ -0,0 +1,202 @@
+//! Accumulation of KZG opening claims, deferring the pairing checks.
+//!
+//! The claim `p(z) = y` for the commitment `C` with the proof `pi` holds when
+//! `e(C - [y]_1 + z pi, [1]_2) = e(pi, [tau]_2)`. An accumulator is a pair
+//! `(L, R)` of G1 elements for which `e(L, [1]_2) = e(R, [tau]_2)` is
+//! expected to hold. Claims and other accumulators are folded into it with
+//! random linear combinations, which keep the relation and break it with
+//! overwhelming probability if any folded claim is false, so that a single
+//! pairing check at the end decides all of them.
+
+use ark_ec::{pairing::Pairing, CurveGroup, VariableBaseMSM};
+use ark_ff::One;
+use ark_std::{ops::*, Zero};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    backend::kzg::{KZGCommitmentScheme, KZGOpenProof, KZGOpeningClaim},
+    errs::{KZGError, KZGResult},
+    primitives::{
+        serde::{ark_deserialize, ark_serialize},
+        transcript::Transcript,
+    },
+};
+
+/// An accumulator of KZG opening claims.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KZGAccumulator<P: Pairing> {
+    /// The element paired with `[1]_2`.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub lhs: P::G1,
+    /// The element paired with `[tau]_2`.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub rhs: P::G1,
+}
+
+impl<P: Pairing> Default for KZGAccumulator<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: Pairing> KZGAccumulator<P> {
+    /// Return the empty accumulator, which is trivially valid.
+    pub fn new() -> Self {
+        Self {
+            lhs: P::G1::zero(),
+            rhs: P::G1::zero(),
+        }
+    }
+
+    fn append_to_transcript(&self, transcript: &mut Transcript) {
+        transcript.append_serializable(b"lhs", &self.lhs);
+        transcript.append_serializable(b"rhs", &self.rhs);
+    }
+
+    /// Fold the opening claims with their proofs into the accumulator.
+    pub fn accumulate(
+        &self,
+        pcs: &KZGCommitmentScheme<P>,
+        claims: &[KZGOpeningClaim<P>],
+        proofs: &[KZGOpenProof<P::G1>],
+    ) -> KZGResult<Self> {
+        if claims.len() != proofs.len() {
+            return Err(KZGError::IndexError);
+        }
+        let mut transcript = Transcript::new(b"kzg-accumulate");
+        self.append_to_transcript(&mut transcript);
+        for (claim, proof) in claims.iter().zip(proofs) {
+            claim.append_to_transcript(&mut transcript);
+            transcript.append_serializable(b"proof", &proof.0);
+        }
+        let r: P::ScalarField = transcript.challenge_scalar(b"r");
+
+        // L + sum_i r^(i+1) (C_i - [y_i]_1 + z_i pi_i) and R + sum_i r^(i+1) pi_i
+        let mut lhs_bases = vec![self.lhs];
+        let mut lhs_scalars = vec![P::ScalarField::one()];
+        let mut rhs_bases = vec![self.rhs];
+        let mut rhs_scalars = vec![P::ScalarField::one()];
+        let mut value = P::ScalarField::zero();
+        let mut power = P::ScalarField::one();
+        for (claim, proof) in claims.iter().zip(proofs) {
+            power *= r;
+            lhs_bases.push(claim.commitment.0);
+            lhs_scalars.push(power);
+            lhs_bases.push(proof.0);
+            lhs_scalars.push(power * claim.point);
+            rhs_bases.push(proof.0);
+            rhs_scalars.push(power);
+            value += power * claim.value;
+        }
+        lhs_bases.push(pcs.public_parameter_group_1[0]);
+        lhs_scalars.push(value.neg());
+
+        Ok(Self {
+            lhs: P::G1::msm(&P::G1::normalize_batch(&lhs_bases), &lhs_scalars).unwrap(),
+            rhs: P::G1::msm(&P::G1::normalize_batch(&rhs_bases), &rhs_scalars).unwrap(),
+        })
+    }
+
+    /// Merge accumulators into one.
+    pub fn merge(accumulators: &[Self]) -> Self {
+        let mut transcript = Transcript::new(b"kzg-merge");
+        for accumulator in accumulators {
+            accumulator.append_to_transcript(&mut transcript);
+        }
+        let r: P::ScalarField = transcript.challenge_scalar(b"r");
+
+        let mut merged = Self::new();
+        let mut power = P::ScalarField::one();
+        for accumulator in accumulators {
+            merged.lhs += accumulator.lhs * power;
+            merged.rhs += accumulator.rhs * power;
+            power *= r;
+        }
+        merged
+    }
+
+    /// Run the deferred pairing check: `e(L, [1]_2) = e(R, [tau]_2)`.
+    pub fn decide(&self, pcs: &KZGCommitmentScheme<P>) -> KZGResult<()> {
+        let g2_0 = pcs.public_parameter_group_2[0];
+        let g2_1 = pcs.public_parameter_group_2[1];
+        if P::multi_pairing([self.lhs, self.rhs.neg()], [g2_0, g2_1]).is_zero() {
+            Ok(())
+        } else {
+            Err(KZGError::PCSProveEvalError)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_accumulation {
+    use super::KZGAccumulator;
+    use crate::{
+        backend::kzg::{KZGCommitmentScheme, KZGOpenProof, KZGOpeningClaim},
+        primitives::poly::FpPolynomial,
+        PolyComScheme,
+    };
+    use ark_bls12_381::Bls12_381;
+    use ark_bn254::Bn254;
+    use ark_ec::pairing::Pairing;
+    use ark_ff::{One, UniformRand};
+    use ark_std::{rand::RngCore, test_rng};
+
+    fn open<P: Pairing, R: RngCore>(
+        pcs: &KZGCommitmentScheme<P>,
+        num: usize,
+        prng: &mut R,
+    ) -> (Vec<KZGOpeningClaim<P>>, Vec<KZGOpenProof<P::G1>>) {
+        (0..num)
+            .map(|_| {
+                let poly = FpPolynomial::random(prng, 7);
+                let point = P::ScalarField::rand(prng);
+                let claim =
+                    KZGOpeningClaim::new(pcs.commit(&poly).unwrap(), point, poly.eval(&point));
+                let proof = KZGOpenProof(pcs.prove(&poly, &point, 7).unwrap().0);
+                (claim, proof)
+            })
+            .unzip()
+    }
+
+    fn check_accumulation<P: Pairing>() {
+        let mut prng = test_rng();
+        let pcs = KZGCommitmentScheme::<P>::new(7, &mut prng);
+        let verifier = pcs.shrink_to_verifier_only().unwrap();
+        assert!(KZGAccumulator::<P>::new().decide(&verifier).is_ok());
+
+        // Two blocks of claims accumulated separately, then merged.
+        let (claims1, proofs1) = open(&pcs, 3, &mut prng);
+        let (claims2, proofs2) = open(&pcs, 4, &mut prng);
+        let acc1 = KZGAccumulator::new()
+            .accumulate(&verifier, &claims1[..1], &proofs1[..1])
+            .unwrap()
+            .accumulate(&verifier, &claims1[1..], &proofs1[1..])
+            .unwrap();
+        let acc2 = KZGAccumulator::new()
+            .accumulate(&verifier, &claims2, &proofs2)
+            .unwrap();
+        assert!(acc1.decide(&verifier).is_ok());
+        assert!(acc2.decide(&verifier).is_ok());
+        let merged = KZGAccumulator::merge(&[acc1.clone(), acc2.clone()]);
+        assert!(merged.decide(&verifier).is_ok());
+
+        // A false claim spoils the accumulator and everything merged with it.
+        let mut wrong_claims = claims2.clone();
+        wrong_claims[2].value += P::ScalarField::one();
+        let wrong = KZGAccumulator::new()
+            .accumulate(&verifier, &wrong_claims, &proofs2)
+            .unwrap();
+        assert!(wrong.decide(&verifier).is_err());
+        let wrong_merged = KZGAccumulator::merge(&[acc1.clone(), wrong]);
+        assert!(wrong_merged.decide(&verifier).is_err());
+        let wrong_acc = acc1.accumulate(&verifier, &wrong_claims, &proofs2).unwrap();
+        assert!(wrong_acc.decide(&verifier).is_err());
+        assert!(acc1.accumulate(&verifier, &claims2, &proofs2[1..]).is_err());
+    }
+
+    #[test]
+    fn test_accumulation() {
+        check_accumulation::<Bn254>();
+        check_accumulation::<Bls12_381>();
+    }
+}
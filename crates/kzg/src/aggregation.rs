@@ -0,0 +1,216 @@
+//! Aggregation of KZG opening proofs produced by different provers.
+//!
+//! The claims are combined with the powers of a challenge `r` derived from
+//! all of them. At a common point `z`, the proof `sum_i r^i pi_i` opens
+//! `sum_i r^i C_i` to `sum_i r^i y_i`, so the openings aggregate into a
+//! single group element. At different points, the proofs alone are only
+//! accumulated and checked together with the single pairing equation
+//! `e(sum_i r^i (C_i - [y_i]_1 + z_i pi_i), [1]_2) = e(sum_i r^i pi_i, [tau]_2)`,
+//! while the party gathering the polynomials from the provers opens all the
+//! claims with a single proof of two group elements, by reducing them to an
+//! opening at a random point (see `KZGCommitmentScheme::prove_multi_point`).
+
+use ark_ec::{pairing::Pairing, CurveGroup, VariableBaseMSM};
+use ark_ff::One;
+
+use crate::{
+    accumulation::KZGAccumulator,
+    backend::kzg::{
+        KZGCommitment, KZGCommitmentScheme, KZGMultiPointProof, KZGOpenProof, KZGOpeningClaim,
+    },
+    errs::{KZGError, KZGResult},
+    primitives::{poly::FpPolynomial, transcript::Transcript},
+    PolyComScheme,
+};
+
+/// Return the powers `1, r, ..., r^(n-1)` of the challenge binding the
+/// claims.
+fn challenge_powers<P: Pairing>(claims: &[KZGOpeningClaim<P>]) -> Vec<P::ScalarField> {
+    let mut transcript = Transcript::new(b"kzg-aggregate");
+    for claim in claims {
+        claim.append_to_transcript(&mut transcript);
+    }
+    let r: P::ScalarField = transcript.challenge_scalar(b"r");
+    let mut powers = Vec::with_capacity(claims.len());
+    let mut power = P::ScalarField::one();
+    for _ in 0..claims.len() {
+        powers.push(power);
+        power *= r;
+    }
+    powers
+}
+
+/// Return the common point of the claims.
+fn common_point<P: Pairing>(claims: &[KZGOpeningClaim<P>]) -> KZGResult<P::ScalarField> {
+    let point = claims.first().ok_or(KZGError::IndexError)?.point;
+    if claims.iter().any(|claim| claim.point != point) {
+        return Err(KZGError::PointMismatchError);
+    }
+    Ok(point)
+}
+
+/// Aggregate the proofs of claims at the same point into a single proof.
+pub fn aggregate_same_point<P: Pairing>(
+    claims: &[KZGOpeningClaim<P>],
+    proofs: &[KZGOpenProof<P::G1>],
+) -> KZGResult<KZGOpenProof<P::G1>> {
+    if claims.len() != proofs.len() {
+        return Err(KZGError::IndexError);
+    }
+    common_point(claims)?;
+    let powers = challenge_powers(claims);
+    let bases: Vec<P::G1> = proofs.iter().map(|proof| proof.0).collect();
+    Ok(KZGOpenProof(
+        P::G1::msm(&P::G1::normalize_batch(&bases), &powers).unwrap(),
+    ))
+}
+
+/// Verify a proof aggregated with `aggregate_same_point`.
+pub fn verify_aggregated_same_point<P: Pairing>(
+    pcs: &KZGCommitmentScheme<P>,
+    claims: &[KZGOpeningClaim<P>],
+    proof: &KZGOpenProof<P::G1>,
+) -> KZGResult<()> {
+    let point = common_point(claims)?;
+    let powers = challenge_powers(claims);
+    let bases: Vec<P::G1> = claims.iter().map(|claim| claim.commitment.0).collect();
+    let commitment = P::G1::msm(&P::G1::normalize_batch(&bases), &powers).unwrap();
+    let value = claims
+        .iter()
+        .zip(&powers)
+        .map(|(claim, r)| claim.value * r)
+        .sum();
+    pcs.verify(
+        &KZGCommitment(commitment),
+        pcs.max_degree(),
+        &point,
+        &value,
+        &KZGCommitment(proof.0),
+    )
+}
+
+/// Aggregate the openings of `polys` at the points of the claims, which may
+/// differ, into a single proof.
+pub fn aggregate_different_points<P: Pairing>(
+    pcs: &KZGCommitmentScheme<P>,
+    polys: &[FpPolynomial<P::ScalarField>],
+    claims: &[KZGOpeningClaim<P>],
+) -> KZGResult<KZGMultiPointProof<P::G1>> {
+    let mut transcript = Transcript::new(b"kzg-aggregate-multi-point");
+    pcs.prove_multi_point(&mut transcript, polys, claims)
+}
+
+/// Verify a proof aggregated with `aggregate_different_points`.
+pub fn verify_aggregated_different_points<P: Pairing>(
+    pcs: &KZGCommitmentScheme<P>,
+    claims: &[KZGOpeningClaim<P>],
+    proof: &KZGMultiPointProof<P::G1>,
+) -> KZGResult<()> {
+    let mut transcript = Transcript::new(b"kzg-aggregate-multi-point");
+    pcs.verify_multi_point(&mut transcript, claims, proof)
+}
+
+/// Verify the proofs of claims at arbitrary points with a single pairing
+/// equation, by accumulating them.
+pub fn batch_verify<P: Pairing>(
+    pcs: &KZGCommitmentScheme<P>,
+    claims: &[KZGOpeningClaim<P>],
+    proofs: &[KZGOpenProof<P::G1>],
+) -> KZGResult<()> {
+    KZGAccumulator::new()
+        .accumulate(pcs, claims, proofs)?
+        .decide(pcs)
+}
+
+#[cfg(test)]
+mod tests_aggregation {
+    use super::{
+        aggregate_different_points, aggregate_same_point, batch_verify,
+        verify_aggregated_different_points, verify_aggregated_same_point,
+    };
+    use crate::{
+        backend::kzg::{KZGCommitmentScheme, KZGOpenProof, KZGOpeningClaim},
+        errs::KZGError,
+        primitives::poly::FpPolynomial,
+        PolyComScheme,
+    };
+    use ark_bls12_381::Bls12_381;
+    use ark_bn254::Bn254;
+    use ark_ec::pairing::Pairing;
+    use ark_ff::{One, UniformRand};
+    use ark_std::test_rng;
+
+    #[allow(clippy::type_complexity)]
+    fn open<P: Pairing>(
+        pcs: &KZGCommitmentScheme<P>,
+        points: &[P::ScalarField],
+    ) -> (
+        Vec<FpPolynomial<P::ScalarField>>,
+        Vec<KZGOpeningClaim<P>>,
+        Vec<KZGOpenProof<P::G1>>,
+    ) {
+        let mut prng = test_rng();
+        let (mut polys, mut claims, mut proofs) = (vec![], vec![], vec![]);
+        for point in points {
+            let poly = FpPolynomial::random(&mut prng, 15);
+            claims.push(KZGOpeningClaim::new(
+                pcs.commit(&poly).unwrap(),
+                *point,
+                poly.eval(point),
+            ));
+            proofs.push(KZGOpenProof(pcs.prove(&poly, point, 15).unwrap().0));
+            polys.push(poly);
+        }
+        (polys, claims, proofs)
+    }
+
+    fn check_aggregation<P: Pairing>() {
+        let mut prng = test_rng();
+        let pcs = KZGCommitmentScheme::<P>::new(15, &mut prng);
+        let verifier = pcs.shrink_to_verifier_only().unwrap();
+
+        // Same point.
+        let point = P::ScalarField::rand(&mut prng);
+        let (_, mut claims, proofs) = open(&pcs, &[point; 5]);
+        let proof = aggregate_same_point(&claims, &proofs).unwrap();
+        assert!(verify_aggregated_same_point(&verifier, &claims, &proof).is_ok());
+        assert!(batch_verify(&verifier, &claims, &proofs).is_ok());
+        claims[3].value += P::ScalarField::one();
+        assert!(verify_aggregated_same_point(&verifier, &claims, &proof).is_err());
+
+        // Different points.
+        let points: Vec<P::ScalarField> = (0..5).map(|_| P::ScalarField::rand(&mut prng)).collect();
+        let (polys, mut claims, mut proofs) = open(&pcs, &points);
+        assert!(batch_verify(&verifier, &claims, &proofs).is_ok());
+        let proof = aggregate_different_points(&pcs, &polys, &claims).unwrap();
+        assert!(verify_aggregated_different_points(&verifier, &claims, &proof).is_ok());
+        assert!(verify_aggregated_different_points(&verifier, &claims[1..], &proof).is_err());
+        let mut wrong = claims.clone();
+        wrong[4].value += P::ScalarField::one();
+        assert!(aggregate_different_points(&pcs, &polys, &wrong).is_err());
+        assert!(verify_aggregated_different_points(&verifier, &wrong, &proof).is_err());
+        assert!(matches!(
+            aggregate_same_point(&claims, &proofs),
+            Err(KZGError::PointMismatchError)
+        ));
+        proofs.swap(1, 2);
+        assert!(batch_verify(&verifier, &claims, &proofs).is_err());
+        proofs.swap(1, 2);
+        claims[0].point += P::ScalarField::one();
+        assert!(batch_verify(&verifier, &claims, &proofs).is_err());
+        assert!(batch_verify(&verifier, &claims, &proofs[1..]).is_err());
+
+        // Different points, some of them shared.
+        let points = [points[0], points[1], points[0], points[2], points[1]];
+        let (polys, claims, _) = open(&pcs, &points);
+        let proof = aggregate_different_points(&pcs, &polys, &claims).unwrap();
+        assert!(verify_aggregated_different_points(&verifier, &claims, &proof).is_ok());
+        assert!(verify_aggregated_different_points(&verifier, &claims[..4], &proof).is_err());
+    }
+
+    #[test]
+    fn test_aggregation() {
+        check_aggregation::<Bn254>();
+        check_aggregation::<Bls12_381>();
+    }
+}
@@ -0,0 +1,429 @@
+//! Polynomial commitments from FRI (Ben-Sasson et al., "Fast Reed-Solomon
+//! interactive oracle proofs of proximity").
+//!
+//! A polynomial of fewer than `n` coefficients is committed to with the
+//! Merkle root of its Reed-Solomon codeword, its evaluations on a coset of
+//! size `blowup_factor * n`. The evaluation `f(x) = v` is proven by showing
+//! with FRI that the quotient `(f(X) - v) / (X - x)`, whose codeword the
+//! verifier derives from the one of `f`, is close to a polynomial of degree
+//! below `n`. Each of the `log2(n)` rounds folds the codeword in half with a
+//! random challenge, and the verifier checks the foldings at `num_queries`
+//! random positions. The commitments are hashes, so they are not homomorphic.
+
+use ark_ff::{batch_inversion, PrimeField};
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use ark_serialize::Compress;
+use ark_std::marker::PhantomData;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errs::{KZGError, KZGResult},
+    primitives::{
+        merkle::{hash_leaf, Digest32, MerkleTree},
+        poly::FpPolynomial,
+        serde::{ark_deserialize, ark_serialize},
+        transcript::Transcript,
+    },
+    PolyComScheme, ToBytes,
+};
+
+/// FRI commitment, the Merkle root of the codeword.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FRICommitment<F: PrimeField> {
+    /// The Merkle root.
+    pub root: Digest32,
+    #[serde(skip)]
+    _field: PhantomData<F>,
+}
+
+impl<F: PrimeField> FRICommitment<F> {
+    /// Wrap a Merkle root.
+    pub fn new(root: Digest32) -> Self {
+        Self {
+            root,
+            _field: PhantomData,
+        }
+    }
+}
+
+impl<F: PrimeField> ToBytes for FRICommitment<F> {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.root.to_vec()
+    }
+}
+
+/// FRI evaluation proof.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FRIProof<F: PrimeField> {
+    /// The Merkle roots of the folded codewords.
+    pub layer_roots: Vec<Digest32>,
+    /// The constant polynomial obtained after all the foldings.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub final_value: F,
+    /// For each query, the pairs of opened values of each layer, starting
+    /// with the codeword of the committed polynomial.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub query_values: Vec<Vec<F>>,
+    /// For each query, the authentication paths of the opened pairs.
+    pub query_paths: Vec<Vec<Vec<Digest32>>>,
+}
+
+/// FRI commitment scheme over the field `F`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FRIScheme<F: PrimeField> {
+    poly_len: usize,
+    blowup_factor: usize,
+    num_queries: usize,
+    /// The domain of the codewords, used on the coset `F::GENERATOR * <w>`.
+    domain: Radix2EvaluationDomain<F>,
+}
+
+/// Hash the pair of values at `y` and `-y`, a leaf of the Merkle trees.
+fn hash_pair<F: PrimeField>(a: &F, b: &F) -> Digest32 {
+    let mut buf = Vec::new();
+    a.serialize_with_mode(&mut buf, Compress::Yes).unwrap();
+    b.serialize_with_mode(&mut buf, Compress::Yes).unwrap();
+    hash_leaf(&buf)
+}
+
+/// Build the Merkle tree whose `i`-th leaf is the pair `(v_i, v_{i + n/2})`.
+fn layer_tree<F: PrimeField>(values: &[F]) -> MerkleTree {
+    let (lo, hi) = values.split_at(values.len() / 2);
+    MerkleTree::new(lo.iter().zip(hi).map(|(a, b)| hash_pair(a, b)).collect())
+}
+
+/// Fold the values `a = q(y)` and `b = q(-y)` into `q_e(y^2) + beta * q_o(y^2)`,
+/// given `1 / y` and `1 / 2`.
+fn fold<F: PrimeField>(a: F, b: F, y_inv: F, beta: F, two_inv: F) -> F {
+    ((a + b) + beta * (a - b) * y_inv) * two_inv
+}
+
+impl<F: PrimeField> FRIScheme<F> {
+    /// Create a new instance of a FRI polynomial commitment scheme.
+    /// `max_degree` - max degree of the polynomial, rounded up to a power of two minus one,
+    /// `blowup_factor` - inverse rate of the code, a power of two at least 2,
+    /// `num_queries` - number of queries of the verifier.
+    pub fn new(max_degree: usize, blowup_factor: usize, num_queries: usize) -> KZGResult<Self> {
+        if !blowup_factor.is_power_of_two() || blowup_factor < 2 || num_queries == 0 {
+            return Err(KZGError::DomainError);
+        }
+        let poly_len = (max_degree + 1).next_power_of_two();
+        let domain = FpPolynomial::<F>::evaluation_domain(poly_len * blowup_factor)
+            .ok_or(KZGError::DomainError)?;
+        Ok(Self {
+            poly_len,
+            blowup_factor,
+            num_queries,
+            domain,
+        })
+    }
+
+    /// Return the inverse rate of the code.
+    pub fn blowup_factor(&self) -> usize {
+        self.blowup_factor
+    }
+
+    /// Return the number of queries.
+    pub fn num_queries(&self) -> usize {
+        self.num_queries
+    }
+
+    /// Return the `i`-th point of the `j`-th layer, `(g * w^i)^(2^j)`.
+    fn layer_point(&self, layer: usize, i: usize) -> F {
+        let mut y = F::GENERATOR * self.domain.element(i);
+        for _ in 0..layer {
+            y.square_in_place();
+        }
+        y
+    }
+
+    /// Return the inverses of the first `len` points of the `j`-th layer,
+    /// stepping through them with the inverse of the layer generator.
+    fn layer_point_inverses(&self, layer: usize, len: usize) -> Vec<F> {
+        let mut y_inv = F::GENERATOR.inverse().unwrap();
+        let mut step = self.domain.group_gen_inv();
+        for _ in 0..layer {
+            y_inv.square_in_place();
+            step.square_in_place();
+        }
+        (0..len)
+            .scan(y_inv, |y_inv, _| {
+                let current = *y_inv;
+                *y_inv *= step;
+                Some(current)
+            })
+            .collect()
+    }
+
+    /// Return the codeword of `poly`.
+    fn codeword(&self, poly: &FpPolynomial<F>) -> KZGResult<Vec<F>> {
+        if poly.get_coefs_ref().len() > self.poly_len {
+            return Err(KZGError::DegreeError);
+        }
+        Ok(poly.coset_fft_with_domain(&self.domain, &F::GENERATOR))
+    }
+
+    /// Absorb the statement and return the transcript.
+    fn transcript(commitment: &Digest32, point: &F, value: &F) -> Transcript {
+        let mut transcript = Transcript::new(b"fri");
+        transcript.append_message(b"commitment", commitment);
+        transcript.append_serializable(b"point", point);
+        transcript.append_serializable(b"value", value);
+        transcript
+    }
+
+    /// Squeeze the query positions among the `domain_size / 2` pairs.
+    fn query_indices(&self, transcript: &mut Transcript) -> Vec<usize> {
+        (0..self.num_queries)
+            .map(|_| {
+                let bytes = transcript.challenge_bytes(b"query");
+                let mut index = [0u8; 8];
+                index.copy_from_slice(&bytes[..8]);
+                (u64::from_le_bytes(index) % (self.domain.size() as u64 / 2)) as usize
+            })
+            .collect()
+    }
+}
+
+impl<F: PrimeField> PolyComScheme for FRIScheme<F> {
+    type Field = F;
+    type Commitment = FRICommitment<F>;
+    type Proof = FRIProof<F>;
+
+    fn max_degree(&self) -> usize {
+        self.poly_len - 1
+    }
+
+    fn commit(&self, polynomial: &FpPolynomial<Self::Field>) -> KZGResult<Self::Commitment> {
+        let codeword = self.codeword(polynomial)?;
+        Ok(FRICommitment::new(layer_tree(&codeword).root()))
+    }
+
+    fn eval(&self, poly: &FpPolynomial<Self::Field>, point: &Self::Field) -> Self::Field {
+        poly.eval(point)
+    }
+
+    fn prove(
+        &self,
+        poly: &FpPolynomial<Self::Field>,
+        x: &Self::Field,
+        max_degree: usize,
+    ) -> KZGResult<Self::Proof> {
+        if poly.degree() > max_degree {
+            return Err(KZGError::DegreeError);
+        }
+        let codeword = self.codeword(poly)?;
+        let tree = layer_tree(&codeword);
+        let eval = poly.eval(x);
+
+        // The codeword of the quotient (f(X) - v) / (X - x).
+        let mut denominators: Vec<F> = (0..codeword.len())
+            .map(|i| self.layer_point(0, i) - x)
+            .collect();
+        if denominators.iter().any(|d| d.is_zero()) {
+            return Err(KZGError::PCSProveEvalError);
+        }
+        batch_inversion(&mut denominators);
+        let mut values: Vec<F> = codeword
+            .iter()
+            .zip(&denominators)
+            .map(|(f, d)| (*f - eval) * d)
+            .collect();
+
+        let mut transcript = Self::transcript(&tree.root(), x, &eval);
+        let num_rounds = self.poly_len.trailing_zeros() as usize;
+        let two_inv = F::from(2u64).inverse().unwrap();
+        let mut layers = vec![];
+        let mut layer_roots = vec![];
+        for j in 0..num_rounds {
+            let beta: F = transcript.challenge_scalar(b"beta");
+            let half = values.len() / 2;
+            values = self
+                .layer_point_inverses(j, half)
+                .into_iter()
+                .enumerate()
+                .map(|(i, y_inv)| fold(values[i], values[i + half], y_inv, beta, two_inv))
+                .collect();
+            if j + 1 < num_rounds {
+                let layer = layer_tree(&values);
+                transcript.append_message(b"layer", &layer.root());
+                layer_roots.push(layer.root());
+                layers.push((values.clone(), layer));
+            }
+        }
+        let final_value = values[0];
+        transcript.append_serializable(b"final", &final_value);
+
+        let mut query_values = vec![];
+        let mut query_paths = vec![];
+        for index in self.query_indices(&mut transcript) {
+            let half = codeword.len() / 2;
+            let mut opened = vec![codeword[index], codeword[index + half]];
+            let mut paths = vec![tree.open(index)];
+            let mut index = index;
+            for (layer_values, layer_tree) in layers.iter() {
+                let half = layer_values.len() / 2;
+                index %= half;
+                opened.push(layer_values[index]);
+                opened.push(layer_values[index + half]);
+                paths.push(layer_tree.open(index));
+            }
+            query_values.push(opened);
+            query_paths.push(paths);
+        }
+
+        Ok(FRIProof {
+            layer_roots,
+            final_value,
+            query_values,
+            query_paths,
+        })
+    }
+
+    /// FRI bounds the degree by `max_degree()`, so a larger `degree` is
+    /// rejected.
+    fn verify(
+        &self,
+        cm: &Self::Commitment,
+        degree: usize,
+        point: &Self::Field,
+        eval: &Self::Field,
+        proof: &Self::Proof,
+    ) -> KZGResult<()> {
+        if degree > self.max_degree() {
+            return Err(KZGError::DegreeError);
+        }
+        let num_rounds = self.poly_len.trailing_zeros() as usize;
+        let num_layers = num_rounds.max(1);
+        let two_inv = F::from(2u64).inverse().unwrap();
+        if proof.layer_roots.len() != num_layers - 1
+            || proof.query_values.len() != self.num_queries
+            || proof.query_paths.len() != self.num_queries
+        {
+            return Err(KZGError::PCSProveEvalError);
+        }
+
+        let mut transcript = Self::transcript(&cm.root, point, eval);
+        let mut betas = Vec::with_capacity(num_rounds);
+        for j in 0..num_rounds {
+            if j > 0 {
+                transcript.append_message(b"layer", &proof.layer_roots[j - 1]);
+            }
+            betas.push(transcript.challenge_scalar::<F>(b"beta"));
+        }
+        transcript.append_serializable(b"final", &proof.final_value);
+        let indices = self.query_indices(&mut transcript);
+
+        let roots: Vec<&Digest32> = [&cm.root].into_iter().chain(&proof.layer_roots).collect();
+        for ((index, values), paths) in indices
+            .into_iter()
+            .zip(&proof.query_values)
+            .zip(&proof.query_paths)
+        {
+            if values.len() != 2 * num_layers || paths.len() != num_layers {
+                return Err(KZGError::PCSProveEvalError);
+            }
+            let mut index = index;
+            let mut half = self.domain.size() / 2;
+            let mut folded = None;
+            for (j, (root, path)) in roots.iter().zip(paths).enumerate() {
+                index %= half;
+                let (a, b) = (values[2 * j], values[2 * j + 1]);
+                if !MerkleTree::verify(root, half, index, &hash_pair(&a, &b), path) {
+                    return Err(KZGError::PCSProveEvalError);
+                }
+                let y = self.layer_point(j, index);
+                let (a, b) = if j == 0 {
+                    // Derive the values of the quotient from the ones of f.
+                    let (y_a, y_b) = (y - point, -y - point);
+                    if y_a.is_zero() || y_b.is_zero() {
+                        return Err(KZGError::PCSProveEvalError);
+                    }
+                    (
+                        (a - eval) * y_a.inverse().unwrap(),
+                        (b - eval) * y_b.inverse().unwrap(),
+                    )
+                } else {
+                    (a, b)
+                };
+                if let Some((value, position)) = folded {
+                    let expected = if position < half { a } else { b };
+                    if value != expected {
+                        return Err(KZGError::PCSProveEvalError);
+                    }
+                }
+                if num_rounds == 0 {
+                    folded = Some((a, 0));
+                    if b != a {
+                        return Err(KZGError::PCSProveEvalError);
+                    }
+                } else {
+                    let y_inv = y.inverse().unwrap();
+                    folded = Some((fold(a, b, y_inv, betas[j], two_inv), index));
+                }
+                half /= 2;
+            }
+            if folded.map(|(value, _)| value) != Some(proof.final_value) {
+                return Err(KZGError::PCSProveEvalError);
+            }
+        }
+        Ok(())
+    }
+
+    /// The verifier needs the same parameters.
+    fn shrink_to_verifier_only(&self) -> KZGResult<Self> {
+        Ok(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests_fri {
+    use super::FRIScheme;
+    use crate::{primitives::poly::FpPolynomial, PolyComScheme};
+    use ark_ff::PrimeField;
+    use ark_std::test_rng;
+
+    fn check_fri<F: PrimeField>(max_degree: usize, blowup_factor: usize, num_queries: usize) {
+        let mut prng = test_rng();
+        let pcs = FRIScheme::<F>::new(max_degree, blowup_factor, num_queries).unwrap();
+        let verifier = pcs.shrink_to_verifier_only().unwrap();
+
+        for degree in [0, max_degree / 2, pcs.max_degree()] {
+            let poly = FpPolynomial::random(&mut prng, degree);
+            let point = F::rand(&mut prng);
+            let com = pcs.commit(&poly).unwrap();
+            let eval = pcs.eval(&poly, &point);
+            let proof = pcs.prove(&poly, &point, degree).unwrap();
+            assert!(verifier.verify(&com, degree, &point, &eval, &proof).is_ok());
+            assert!(verifier
+                .verify(&com, pcs.max_degree() + 1, &point, &eval, &proof)
+                .is_err());
+
+            let wrong_eval = eval + F::one();
+            assert!(verifier
+                .verify(&com, degree, &point, &wrong_eval, &proof)
+                .is_err());
+            let mut wrong_proof = proof.clone();
+            wrong_proof.final_value += F::one();
+            assert!(verifier
+                .verify(&com, degree, &point, &eval, &wrong_proof)
+                .is_err());
+            let mut wrong_proof = proof.clone();
+            wrong_proof.query_values[0][0] += F::one();
+            assert!(verifier
+                .verify(&com, degree, &point, &eval, &wrong_proof)
+                .is_err());
+        }
+
+        let poly = FpPolynomial::random(&mut prng, pcs.max_degree() + 1);
+        assert!(pcs.commit(&poly).is_err());
+    }
+
+    #[test]
+    fn test_fri() {
+        check_fri::<ark_bn254::Fr>(15, 2, 8);
+        check_fri::<ark_bn254::Fr>(20, 8, 4);
+        check_fri::<ark_bn254::Fr>(0, 4, 2);
+        check_fri::<ark_bls12_381::Fr>(31, 4, 16);
+    }
+}
@@ -0,0 +1,248 @@
+//! The hiding variant of the KZG commitment scheme from the original KZG10
+//! paper (Kate, Zaverucha and Goldberg, "Constant-size commitments to
+//! polynomials and their applications").
+//!
+//! The SRS has the powers of the secret over a second generator `h` of G1,
+//! with unknown discrete logarithm with respect to `g`. A polynomial `p` is
+//! committed to as `g^p(tau) * h^r(tau)` for a random blinding polynomial
+//! `r`, which makes the commitment perfectly hiding. An opening at `x`
+//! reveals `r(x)` along with the witness `g^psi(tau) * h^psi_r(tau)`, where
+//! `psi` and `psi_r` are the quotients of `p - p(x)` and `r - r(x)` by `X - x`.
+
+use ark_ec::{pairing::Pairing, CurveGroup, VariableBaseMSM};
+use ark_ff::{One, UniformRand};
+use ark_std::{ops::*, rand::RngCore, Zero};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    backend::kzg::KZGCommitment,
+    errs::{KZGError, KZGResult},
+    primitives::{
+        poly::FpPolynomial,
+        serde::{ark_deserialize, ark_serialize},
+    },
+};
+
+/// Hiding KZG opening proof.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct HidingKZGProof<G: CurveGroup> {
+    /// The witness `g^psi(tau) * h^psi_r(tau)`.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub witness: G,
+    /// The evaluation of the blinding polynomial at the point.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub blinding_eval: G::ScalarField,
+}
+
+/// The blinding polynomial of a hiding commitment, kept by the prover for the openings.
+pub type HidingKZGRandomness<F> = FpPolynomial<F>;
+
+/// Hiding KZG commitment scheme about `PairingEngine`.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct HidingKZGCommitmentScheme<P: Pairing> {
+    /// public parameter about G1, the powers `g^(tau^i)`.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub public_parameter_group_1: Vec<P::G1>,
+    /// public parameter about G1, the powers `h^(tau^i)`.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub public_parameter_group_1_h: Vec<P::G1>,
+    /// public parameter about G2.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub public_parameter_group_2: Vec<P::G2>,
+}
+
+impl<P: Pairing> HidingKZGCommitmentScheme<P> {
+    /// Create a new instance of a hiding KZG polynomial commitment scheme.
+    /// `max_degree` - max degree of the polynomial and of the blinding polynomial,
+    /// `prng` - pseudo-random generator.
+    /// WARN only for testing purposes.
+    #[cfg(test)]
+    pub fn new<R: RngCore>(max_degree: usize, prng: &mut R) -> HidingKZGCommitmentScheme<P> {
+        use ark_ec::PrimeGroup;
+        let s = P::ScalarField::rand(prng);
+        let g = P::G1::generator();
+        let h = g.mul(&P::ScalarField::rand(prng));
+
+        let mut public_parameter_group_1 = Vec::with_capacity(max_degree + 1);
+        let mut public_parameter_group_1_h = Vec::with_capacity(max_degree + 1);
+        let mut power = P::ScalarField::one();
+        for _ in 0..=max_degree {
+            public_parameter_group_1.push(g.mul(&power));
+            public_parameter_group_1_h.push(h.mul(&power));
+            power *= s;
+        }
+        let g2 = P::G2::generator();
+
+        HidingKZGCommitmentScheme {
+            public_parameter_group_1,
+            public_parameter_group_1_h,
+            public_parameter_group_2: vec![g2, g2.mul(&s)],
+        }
+    }
+
+    /// Return maximal supported degree.
+    pub fn max_degree(&self) -> usize {
+        self.public_parameter_group_1.len() - 1
+    }
+
+    /// Sample a random blinding polynomial of degree `hiding_degree`. A
+    /// commitment stays hiding after up to `hiding_degree` openings.
+    pub fn sample_blinding<R: RngCore>(
+        &self,
+        hiding_degree: usize,
+        prng: &mut R,
+    ) -> KZGResult<HidingKZGRandomness<P::ScalarField>> {
+        if hiding_degree >= self.public_parameter_group_1_h.len() {
+            return Err(KZGError::DegreeError);
+        }
+        Ok(FpPolynomial::from_coefs(
+            (0..=hiding_degree)
+                .map(|_| P::ScalarField::rand(prng))
+                .collect(),
+        ))
+    }
+
+    /// Commit to the polynomial as `g^p(tau) * h^r(tau)`, with the blinding
+    /// polynomial `r`.
+    pub fn commit(
+        &self,
+        polynomial: &FpPolynomial<P::ScalarField>,
+        blinding: &HidingKZGRandomness<P::ScalarField>,
+    ) -> KZGResult<KZGCommitment<P::G1>> {
+        let coefs = polynomial.get_coefs_ref();
+        let blinding_coefs = blinding.get_coefs_ref();
+        if coefs.len() > self.public_parameter_group_1.len()
+            || blinding_coefs.len() > self.public_parameter_group_1_h.len()
+        {
+            return Err(KZGError::DegreeError);
+        }
+        let g_powers = P::G1::normalize_batch(&self.public_parameter_group_1[..coefs.len()]);
+        let h_powers =
+            P::G1::normalize_batch(&self.public_parameter_group_1_h[..blinding_coefs.len()]);
+        Ok(KZGCommitment(
+            P::G1::msm(&g_powers, coefs).unwrap() + P::G1::msm(&h_powers, blinding_coefs).unwrap(),
+        ))
+    }
+
+    /// Evaluate the polynomial at `point` producing a proof for it.
+    pub fn prove(
+        &self,
+        polynomial: &FpPolynomial<P::ScalarField>,
+        blinding: &HidingKZGRandomness<P::ScalarField>,
+        point: &P::ScalarField,
+    ) -> KZGResult<HidingKZGProof<P::G1>> {
+        // X - x
+        let divisor = FpPolynomial::from_coefs(vec![point.neg(), P::ScalarField::one()]);
+
+        let eval = polynomial.eval(point);
+        let (quotient, remainder) = polynomial
+            .sub(&FpPolynomial::from_coefs(vec![eval]))
+            .div_rem(&divisor);
+        let blinding_eval = blinding.eval(point);
+        let (blinding_quotient, blinding_remainder) = blinding
+            .sub(&FpPolynomial::from_coefs(vec![blinding_eval]))
+            .div_rem(&divisor);
+        if !remainder.is_zero() || !blinding_remainder.is_zero() {
+            return Err(KZGError::PCSProveEvalError);
+        }
+
+        Ok(HidingKZGProof {
+            witness: self.commit(&quotient, &blinding_quotient)?.0,
+            blinding_eval,
+        })
+    }
+
+    /// Verify an evaluation proof that polynomial inside commitment
+    /// evaluates to `value` on input `point`:
+    /// `e(C / (g^v * h^r(x)), [1]_2) = e(w, [tau - x]_2)`.
+    pub fn verify(
+        &self,
+        cm: &KZGCommitment<P::G1>,
+        point: &P::ScalarField,
+        value: &P::ScalarField,
+        proof: &HidingKZGProof<P::G1>,
+    ) -> KZGResult<()> {
+        let g1_0 = self.public_parameter_group_1[0];
+        let h1_0 = self.public_parameter_group_1_h[0];
+        let g2_0 = self.public_parameter_group_2[0];
+        let g2_1 = self.public_parameter_group_2[1];
+
+        let left = cm.0 - g1_0.mul(value) - h1_0.mul(&proof.blinding_eval);
+        let right = g2_1.sub(&g2_0.mul(point));
+
+        if P::multi_pairing([left, proof.witness.neg()], [g2_0, right]).is_zero() {
+            Ok(())
+        } else {
+            Err(KZGError::PCSProveEvalError)
+        }
+    }
+
+    /// Shrink this to only for verifier use.
+    pub fn shrink_to_verifier_only(&self) -> KZGResult<Self> {
+        Ok(Self {
+            public_parameter_group_1: vec![self.public_parameter_group_1[0]],
+            public_parameter_group_1_h: vec![self.public_parameter_group_1_h[0]],
+            public_parameter_group_2: self.public_parameter_group_2.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests_hiding_kzg {
+    use super::HidingKZGCommitmentScheme;
+    use crate::{primitives::poly::FpPolynomial, HomomorphicPolyComElem};
+    use ark_bls12_381::Bls12_381;
+    use ark_bn254::Bn254;
+    use ark_ec::pairing::Pairing;
+    use ark_ff::{One, UniformRand};
+    use ark_std::test_rng;
+
+    fn check_hiding_kzg<P: Pairing>() {
+        let mut prng = test_rng();
+        let degree = 10;
+        let pcs = HidingKZGCommitmentScheme::<P>::new(degree, &mut prng);
+        let verifier = pcs.shrink_to_verifier_only().unwrap();
+
+        let poly = FpPolynomial::random(&mut prng, degree);
+        let blinding = pcs.sample_blinding(2, &mut prng).unwrap();
+        let com = pcs.commit(&poly, &blinding).unwrap();
+        for _ in 0..3 {
+            let point = P::ScalarField::rand(&mut prng);
+            let value = poly.eval(&point);
+            let proof = pcs.prove(&poly, &blinding, &point).unwrap();
+            assert!(verifier.verify(&com, &point, &value, &proof).is_ok());
+
+            let wrong_value = value + P::ScalarField::one();
+            assert!(verifier.verify(&com, &point, &wrong_value, &proof).is_err());
+            let mut wrong_proof = proof.clone();
+            wrong_proof.blinding_eval += P::ScalarField::one();
+            assert!(verifier.verify(&com, &point, &value, &wrong_proof).is_err());
+        }
+
+        // Commitments to the same polynomial differ.
+        let blinding2 = pcs.sample_blinding(2, &mut prng).unwrap();
+        let com2 = pcs.commit(&poly, &blinding2).unwrap();
+        assert_ne!(com, com2);
+
+        // The commitments are homomorphic in the polynomial and the blinding.
+        let poly2 = FpPolynomial::random(&mut prng, degree);
+        let blinding3 = pcs.sample_blinding(2, &mut prng).unwrap();
+        let com3 = pcs.commit(&poly2, &blinding3).unwrap();
+        let sum = poly.add(&poly2);
+        let point = P::ScalarField::rand(&mut prng);
+        let proof = pcs.prove(&sum, &blinding2.add(&blinding3), &point).unwrap();
+        assert!(verifier
+            .verify(&com2.add(&com3), &point, &sum.eval(&point), &proof)
+            .is_ok());
+
+        let poly = FpPolynomial::random(&mut prng, degree + 1);
+        assert!(pcs.commit(&poly, &blinding).is_err());
+        assert!(pcs.sample_blinding(degree + 1, &mut prng).is_err());
+    }
+
+    #[test]
+    fn test_hiding_kzg() {
+        check_hiding_kzg::<Bn254>();
+        check_hiding_kzg::<Bls12_381>();
+    }
+}
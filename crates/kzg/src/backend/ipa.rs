@@ -0,0 +1,414 @@
+//! Polynomial commitments from the inner product argument of Bulletproofs
+//! (Bünz et al.), in the form used by Halo (Bowe, Grigg and Hopwood).
+//!
+//! A polynomial is committed to with a Pedersen vector commitment
+//! `C = <a, G>` to its coefficients, over generators hashed from a public
+//! seed, so that no trusted setup is needed. The evaluation `v = <a, b>` with
+//! `b = (1, x, ..., x^(d-1))` is proven with `log2(d)` rounds, each halving
+//! the vectors and sending two group elements. The verifier does `O(d)`
+//! group operations.
+
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ed_on_bls12_381_bandersnatch::EdwardsProjective;
+use ark_ff::{Field, One, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    errs::{KZGError, KZGResult},
+    primitives::{
+        poly::FpPolynomial,
+        serde::{ark_deserialize, ark_serialize},
+        transcript::Transcript,
+    },
+    HomomorphicPolyComElem, HomomorphicPolyComScheme, PolyComScheme, ToBytes,
+};
+
+/// IPA commitment, a Pedersen vector commitment to the coefficients.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Default)]
+pub struct IPACommitment<G: CanonicalSerialize + CanonicalDeserialize>(
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")] pub G,
+);
+
+impl<G: CurveGroup> ToBytes for IPACommitment<G> {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes()
+    }
+}
+
+impl<G: CurveGroup> HomomorphicPolyComElem for IPACommitment<G> {
+    type Scalar = G::ScalarField;
+    fn get_base() -> Self {
+        IPACommitment(G::generator())
+    }
+
+    fn get_identity() -> Self {
+        IPACommitment(G::zero())
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        IPACommitment(self.0.add(&other.0))
+    }
+
+    fn add_assign(&mut self, other: &Self) {
+        self.0.add_assign(&other.0)
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        IPACommitment(self.0.sub(&other.0))
+    }
+
+    fn sub_assign(&mut self, other: &Self) {
+        self.0.sub_assign(&other.0)
+    }
+
+    fn mul(&self, exp: &G::ScalarField) -> Self {
+        IPACommitment(self.0.mul(exp))
+    }
+
+    fn mul_assign(&mut self, exp: &G::ScalarField) {
+        self.0.mul_assign(exp)
+    }
+}
+
+/// IPA evaluation proof.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct IPAProof<G: CurveGroup> {
+    /// The cross terms `L_j`.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub l_vec: Vec<G>,
+    /// The cross terms `R_j`.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub r_vec: Vec<G>,
+    /// The last coefficient after all the foldings.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub a: G::ScalarField,
+}
+
+/// IPA commitment scheme over the `Group`.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct IPACommitmentScheme<G: CurveGroup> {
+    /// The generators `G_i` of the vector commitment.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub generators: Vec<G>,
+    /// The generator `U` binding the inner product.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub u: G,
+}
+
+impl<G: CurveGroup> IPACommitmentScheme<G> {
+    /// Create a new instance of an IPA polynomial commitment scheme.
+    /// `max_degree` - max degree of the polynomial, rounded up to a power of two minus one,
+    /// `seed` - public seed from which the generators are hashed, without known
+    /// discrete logarithms between them.
+    pub fn new(max_degree: usize, seed: &[u8]) -> Self {
+        let size = (max_degree + 1).next_power_of_two();
+        let mut generators = Self::hash_to_generators(seed, size + 1);
+        let u = generators.pop().unwrap();
+        Self { generators, u }
+    }
+
+    /// Hash `seed` to `count` generators by try-and-increment: the SHA-256
+    /// hashes of the seed, the index of the generator and a counter are read
+    /// as the coordinate of a point until one is on the curve, whose cofactor
+    /// is then cleared.
+    fn hash_to_generators(seed: &[u8], count: usize) -> Vec<G> {
+        let seed = Sha256::digest(seed);
+        (0..count as u64)
+            .map(|index| {
+                (0u64..)
+                    .find_map(|counter| {
+                        let mut bytes = Vec::with_capacity(64);
+                        for half in [0u8, 1] {
+                            let mut hasher = Sha256::new();
+                            hasher.update(b"ipa-generator");
+                            hasher.update(seed);
+                            hasher.update(index.to_le_bytes());
+                            hasher.update(counter.to_le_bytes());
+                            hasher.update([half]);
+                            bytes.extend(hasher.finalize());
+                        }
+                        G::Affine::from_random_bytes(&bytes)
+                            .map(|point| point.clear_cofactor())
+                            .filter(|point| !point.is_zero())
+                    })
+                    .unwrap()
+                    .into_group()
+            })
+            .collect()
+    }
+
+    /// Absorb the statement and return the transcript.
+    fn transcript(commitment: &G, point: &G::ScalarField, value: &G::ScalarField) -> Transcript {
+        let mut transcript = Transcript::new(b"ipa");
+        transcript.append_serializable(b"commitment", commitment);
+        transcript.append_serializable(b"point", point);
+        transcript.append_serializable(b"value", value);
+        transcript
+    }
+
+    fn msm(bases: &[G], scalars: &[G::ScalarField]) -> G {
+        G::msm(&G::normalize_batch(bases), scalars).unwrap()
+    }
+
+    fn inner_product(a: &[G::ScalarField], b: &[G::ScalarField]) -> G::ScalarField {
+        a.iter().zip(b).map(|(a, b)| *a * b).sum()
+    }
+}
+
+/// IPA commitment scheme over the Bandersnatch curve
+pub type IPACommitmentSchemeBandersnatch = IPACommitmentScheme<EdwardsProjective>;
+
+impl<G: CurveGroup> PolyComScheme for IPACommitmentScheme<G> {
+    type Field = G::ScalarField;
+    type Commitment = IPACommitment<G>;
+    type Proof = IPAProof<G>;
+
+    fn max_degree(&self) -> usize {
+        self.generators.len() - 1
+    }
+
+    fn commit(&self, polynomial: &FpPolynomial<Self::Field>) -> KZGResult<Self::Commitment> {
+        let coefs = polynomial.get_coefs_ref();
+        if coefs.len() > self.generators.len() {
+            return Err(KZGError::DegreeError);
+        }
+        Ok(IPACommitment(Self::msm(
+            &self.generators[..coefs.len()],
+            coefs,
+        )))
+    }
+
+    fn eval(&self, poly: &FpPolynomial<Self::Field>, point: &Self::Field) -> Self::Field {
+        poly.eval(point)
+    }
+
+    fn prove(
+        &self,
+        poly: &FpPolynomial<Self::Field>,
+        x: &Self::Field,
+        max_degree: usize,
+    ) -> KZGResult<Self::Proof> {
+        if poly.degree() > max_degree {
+            return Err(KZGError::DegreeError);
+        }
+        let commitment = self.commit(poly)?.0;
+        let eval = poly.eval(x);
+
+        let size = self.generators.len();
+        let mut a = poly.get_coefs_ref().to_vec();
+        a.resize(size, Self::Field::zero());
+        let mut b = Vec::with_capacity(size);
+        let mut power = Self::Field::one();
+        for _ in 0..size {
+            b.push(power);
+            power *= x;
+        }
+        let mut generators = self.generators.clone();
+
+        let mut transcript = Self::transcript(&commitment, x, &eval);
+        let u = self.u * transcript.challenge_scalar::<Self::Field>(b"u");
+
+        let mut l_vec = Vec::new();
+        let mut r_vec = Vec::new();
+        while a.len() > 1 {
+            let half = a.len() / 2;
+            let (a_lo, a_hi) = a.split_at(half);
+            let (b_lo, b_hi) = b.split_at(half);
+            let (g_lo, g_hi) = generators.split_at(half);
+
+            let l = Self::msm(g_hi, a_lo) + u * Self::inner_product(a_lo, b_hi);
+            let r = Self::msm(g_lo, a_hi) + u * Self::inner_product(a_hi, b_lo);
+            transcript.append_serializable(b"L", &l);
+            transcript.append_serializable(b"R", &r);
+            l_vec.push(l);
+            r_vec.push(r);
+
+            let c: Self::Field = transcript.challenge_scalar(b"round");
+            let c_inv = c.inverse().ok_or(KZGError::PCSProveEvalError)?;
+            a = a_lo
+                .iter()
+                .zip(a_hi)
+                .map(|(lo, hi)| *lo * c + *hi * c_inv)
+                .collect();
+            b = b_lo
+                .iter()
+                .zip(b_hi)
+                .map(|(lo, hi)| *lo * c_inv + *hi * c)
+                .collect();
+            generators = g_lo
+                .iter()
+                .zip(g_hi)
+                .map(|(lo, hi)| *lo * c_inv + *hi * c)
+                .collect();
+        }
+
+        Ok(IPAProof {
+            l_vec,
+            r_vec,
+            a: a[0],
+        })
+    }
+
+    fn verify(
+        &self,
+        cm: &Self::Commitment,
+        _degree: usize,
+        point: &Self::Field,
+        eval: &Self::Field,
+        proof: &Self::Proof,
+    ) -> KZGResult<()> {
+        let size = self.generators.len();
+        let rounds = size.trailing_zeros() as usize;
+        if proof.l_vec.len() != rounds || proof.r_vec.len() != rounds {
+            return Err(KZGError::PCSProveEvalError);
+        }
+
+        let mut transcript = Self::transcript(&cm.0, point, eval);
+        let u = self.u * transcript.challenge_scalar::<Self::Field>(b"u");
+
+        let mut challenges = Vec::with_capacity(rounds);
+        for (l, r) in proof.l_vec.iter().zip(&proof.r_vec) {
+            transcript.append_serializable(b"L", l);
+            transcript.append_serializable(b"R", r);
+            let c: Self::Field = transcript.challenge_scalar(b"round");
+            let c_inv = c.inverse().ok_or(KZGError::PCSProveEvalError)?;
+            challenges.push((c, c_inv));
+        }
+
+        // The folded generator is sum_i s_i G_i, where s_i multiplies c_j or
+        // c_j^-1 depending on the j-th most significant bit of i.
+        let mut s = vec![Self::Field::one()];
+        for (c, c_inv) in challenges.iter() {
+            s = s.iter().flat_map(|s| [*s * c_inv, *s * c]).collect();
+        }
+        // The folded b = prod_j (c_j^-1 + c_j x^(d / 2^(j+1))).
+        let mut x_power = *point;
+        let mut b = Self::Field::one();
+        for (c, c_inv) in challenges.iter().rev() {
+            b *= *c_inv + *c * x_power;
+            x_power.square_in_place();
+        }
+
+        // C + v U + sum_j (c_j^2 L_j + c_j^-2 R_j) = a <s, G> + a b U
+        let mut bases = proof.l_vec.clone();
+        bases.extend(&proof.r_vec);
+        bases.extend(&self.generators);
+        bases.push(u);
+        let mut scalars: Vec<Self::Field> = challenges.iter().map(|(c, _)| c.square()).collect();
+        scalars.extend(challenges.iter().map(|(_, c_inv)| c_inv.square()));
+        scalars.extend(s.iter().map(|s| -proof.a * s));
+        scalars.push(*eval - proof.a * b);
+
+        if (cm.0 + Self::msm(&bases, &scalars)).is_zero() {
+            Ok(())
+        } else {
+            Err(KZGError::PCSProveEvalError)
+        }
+    }
+
+    /// The verifier needs all the generators.
+    fn shrink_to_verifier_only(&self) -> KZGResult<Self> {
+        Ok(self.clone())
+    }
+}
+
+impl<G: CurveGroup> HomomorphicPolyComScheme for IPACommitmentScheme<G> {
+    fn apply_blind_factors(
+        &self,
+        commitment: &Self::Commitment,
+        blinds: &[Self::Field],
+        zeroing_degree: usize,
+    ) -> Self::Commitment {
+        let mut commitment = commitment.0;
+        for (i, blind) in blinds.iter().enumerate() {
+            commitment += self.generators[i] * blind;
+            commitment -= self.generators[zeroing_degree + i] * blind;
+        }
+        IPACommitment(commitment)
+    }
+}
+
+#[cfg(test)]
+mod tests_ipa {
+    use super::IPACommitmentScheme;
+    use crate::{primitives::poly::FpPolynomial, HomomorphicPolyComElem, PolyComScheme};
+    use ark_ec::CurveGroup;
+    use ark_ff::{One, UniformRand};
+    use ark_std::test_rng;
+
+    fn check_ipa<G: CurveGroup>() {
+        let mut prng = test_rng();
+        let pcs = IPACommitmentScheme::<G>::new(12, b"test");
+        assert_eq!(pcs.max_degree(), 15);
+        assert_eq!(pcs, IPACommitmentScheme::<G>::new(12, b"test"));
+
+        for degree in [0, 5, 15] {
+            let poly = FpPolynomial::random(&mut prng, degree);
+            let point = G::ScalarField::rand(&mut prng);
+            let com = pcs.commit(&poly).unwrap();
+            let eval = pcs.eval(&poly, &point);
+            let proof = pcs.prove(&poly, &point, degree).unwrap();
+            assert!(pcs.verify(&com, degree, &point, &eval, &proof).is_ok());
+
+            let wrong_eval = eval + G::ScalarField::one();
+            assert!(pcs
+                .verify(&com, degree, &point, &wrong_eval, &proof)
+                .is_err());
+            let mut wrong_proof = proof.clone();
+            wrong_proof.a += G::ScalarField::one();
+            assert!(pcs
+                .verify(&com, degree, &point, &eval, &wrong_proof)
+                .is_err());
+        }
+
+        // The commitments are homomorphic.
+        let p1 = FpPolynomial::random(&mut prng, 7);
+        let p2 = FpPolynomial::random(&mut prng, 7);
+        assert_eq!(
+            pcs.commit(&p1.add(&p2)).unwrap(),
+            pcs.commit(&p1).unwrap().add(&pcs.commit(&p2).unwrap())
+        );
+
+        let poly = FpPolynomial::random(&mut prng, 16);
+        assert!(pcs.commit(&poly).is_err());
+    }
+
+    #[test]
+    fn test_generators() {
+        use ark_bn254::{Fq, G1Affine, G1Projective};
+        use ark_std::str::FromStr;
+
+        // The generators do not depend on the version of any random number
+        // generator.
+        let pcs = IPACommitmentScheme::<G1Projective>::new(3, b"test");
+        let point = |x: &str, y: &str| {
+            G1Projective::from(G1Affine::new(
+                Fq::from_str(x).unwrap(),
+                Fq::from_str(y).unwrap(),
+            ))
+        };
+        assert_eq!(
+            pcs.generators[0],
+            point(
+                "12656673229055742604049872196239215078155076063587774063875149123760934174500",
+                "13065881202450502449785498637518341057876662097918647249528631735854980158763"
+            )
+        );
+        assert_eq!(
+            pcs.u,
+            point(
+                "20527620231370770040069441486035270561035342691904433482748949229547702327847",
+                "16283307073540562926132602180542346961851863408402005082501825877089252155023"
+            )
+        );
+    }
+
+    #[test]
+    fn test_ipa() {
+        check_ipa::<ark_bn254::G1Projective>();
+        check_ipa::<ark_bls12_381::G1Projective>();
+        check_ipa::<ark_ed_on_bls12_381_bandersnatch::EdwardsProjective>();
+    }
+}
@@ -0,0 +1,938 @@
+use crate::{
+    errs::{KZGError, KZGResult},
+    fk20::FK20,
+    primitives::{
+        poly::FpPolynomial,
+        serde::{ark_deserialize, ark_serialize},
+        transcript::Transcript,
+    },
+    HomomorphicPolyComElem, HomomorphicPolyComScheme, PolyComScheme, ToBytes,
+};
+use ark_bls12_381::Bls12_381;
+use ark_bn254::Bn254;
+use ark_ec::{pairing::Pairing, CurveGroup, PrimeGroup, VariableBaseMSM};
+use ark_ff::{batch_inversion, Field, One, PrimeField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+use ark_std::{ops::*, Zero};
+use serde::{Deserialize, Serialize};
+
+/// KZG commitment scheme over the `Group`.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Default)]
+pub struct KZGCommitment<G: CanonicalSerialize + CanonicalDeserialize>(
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")] pub G,
+);
+
+impl<G> ToBytes for G
+where
+    G: CurveGroup,
+{
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.serialize_with_mode(&mut buf, Compress::Yes).unwrap();
+        buf
+    }
+}
+
+impl<G> ToBytes for KZGCommitment<G>
+where
+    G: CurveGroup,
+{
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes()
+    }
+}
+
+impl<G: CurveGroup> HomomorphicPolyComElem for KZGCommitment<G> {
+    type Scalar = G::ScalarField;
+    fn get_base() -> Self {
+        KZGCommitment(G::generator())
+    }
+
+    fn get_identity() -> Self {
+        KZGCommitment(G::zero())
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        KZGCommitment(self.0.add(&other.0))
+    }
+
+    fn add_assign(&mut self, other: &Self) {
+        self.0.add_assign(&other.0)
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        KZGCommitment(self.0.sub(&other.0))
+    }
+
+    fn sub_assign(&mut self, other: &Self) {
+        self.0.sub_assign(&other.0)
+    }
+
+    fn mul(&self, exp: &G::ScalarField) -> Self {
+        KZGCommitment(self.0.mul(exp))
+    }
+
+    fn mul_assign(&mut self, exp: &G::ScalarField) {
+        self.0.mul_assign(exp)
+    }
+}
+
+impl<F: PrimeField> ToBytes for FpPolynomial<F> {
+    fn to_bytes(&self) -> Vec<u8> {
+        unimplemented!()
+    }
+}
+
+impl<F: PrimeField> HomomorphicPolyComElem for FpPolynomial<F> {
+    type Scalar = F;
+
+    fn get_base() -> Self {
+        unimplemented!()
+    }
+
+    fn get_identity() -> Self {
+        unimplemented!()
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        self.add(other)
+    }
+
+    fn add_assign(&mut self, other: &Self) {
+        self.add_assign(other)
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        self.sub(other)
+    }
+
+    fn sub_assign(&mut self, other: &Self) {
+        self.sub_assign(other)
+    }
+
+    fn mul(&self, exp: &F) -> Self {
+        self.mul_scalar(exp)
+    }
+
+    fn mul_assign(&mut self, exp: &F) {
+        self.mul_scalar_assign(exp)
+    }
+}
+
+/// KZG opening proof.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct KZGOpenProof<G1: CanonicalSerialize + CanonicalDeserialize>(
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")] pub G1,
+);
+
+impl<G: PrimeGroup> ToBytes for KZGOpenProof<G> {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.0.serialize_with_mode(&mut buf, Compress::Yes).unwrap();
+        buf
+    }
+}
+
+/// The claim that the polynomial inside `commitment` evaluates to `value`
+/// at `point`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KZGOpeningClaim<P: Pairing> {
+    /// The commitment to the polynomial.
+    pub commitment: KZGCommitment<P::G1>,
+    /// The evaluation point.
+    pub point: P::ScalarField,
+    /// The claimed evaluation.
+    pub value: P::ScalarField,
+}
+
+impl<P: Pairing> KZGOpeningClaim<P> {
+    /// Create a new claim.
+    pub fn new(
+        commitment: KZGCommitment<P::G1>,
+        point: P::ScalarField,
+        value: P::ScalarField,
+    ) -> Self {
+        Self {
+            commitment,
+            point,
+            value,
+        }
+    }
+
+    /// Absorb the claim into the transcript.
+    pub fn append_to_transcript(&self, transcript: &mut Transcript) {
+        transcript.append_serializable(b"commitment", &self.commitment.0);
+        transcript.append_serializable(b"point", &self.point);
+        transcript.append_serializable(b"value", &self.value);
+    }
+}
+
+/// Proof that a committed polynomial has degree at most a bound.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct KZGDegreeBoundProof<G: CurveGroup> {
+    /// The commitment to the shifted polynomial `X^(D - degree) * p`.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub shifted_commitment: G,
+    /// The evaluation `p(z)` at the challenge.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub value: G::ScalarField,
+    /// The opening of `C + gamma * C'` at `z`.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub opening: G,
+}
+
+/// Proof opening commitments at different points with two group elements,
+/// as in Boneh, Drake, Fisch and Gabizon, "Efficient polynomial commitment
+/// schemes for multiple points and polynomials".
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct KZGMultiPointProof<G: CurveGroup> {
+    /// The commitment to `h = sum_i r^i (f_i - y_i) / (X - z_i)`.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub quotient: G,
+    /// The opening at `t` of `sum_i r^i (f_i - y_i) / (t - z_i) - h` to zero.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub opening: G,
+}
+
+/// KZG commitment scheme about `PairingEngine`.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct KZGCommitmentScheme<P: Pairing> {
+    /// public parameter about G1.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub public_parameter_group_1: Vec<P::G1>,
+    /// public parameter about G1.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub public_parameter_group_2: Vec<P::G2>,
+    /// The max degree `D` of the SRS in G1, kept when shrinking to the
+    /// verifier.
+    pub max_degree: usize,
+}
+
+impl<P: Pairing> KZGCommitmentScheme<P> {
+    /// Create a new instance of a KZG polynomial commitment scheme.
+    /// `max_degree` - max degree of the polynomial,
+    /// `prng` - pseudo-random generator.
+    /// WARN only for testing purposes.
+    #[cfg(test)]
+    pub fn new<R: ark_std::rand::RngCore>(
+        max_degree: usize,
+        prng: &mut R,
+    ) -> KZGCommitmentScheme<P> {
+        Self::new_with_g2_degree(max_degree, 1, prng)
+    }
+
+    /// Create a new instance with the powers of the secret in G2 up to
+    /// `max_degree_g2`, as needed to verify openings on cosets.
+    /// WARN only for testing purposes.
+    #[cfg(test)]
+    pub fn new_with_g2_degree<R: ark_std::rand::RngCore>(
+        max_degree: usize,
+        max_degree_g2: usize,
+        prng: &mut R,
+    ) -> KZGCommitmentScheme<P> {
+        use ark_std::UniformRand;
+        let s = P::ScalarField::rand(prng);
+
+        let mut public_parameter_group_1: Vec<P::G1> = Vec::new();
+
+        let mut elem_g1 = P::G1::generator();
+
+        for _ in 0..=max_degree {
+            public_parameter_group_1.push(elem_g1.clone());
+            elem_g1 = elem_g1.mul(&s);
+        }
+
+        let mut public_parameter_group_2: Vec<P::G2> = Vec::new();
+        let mut elem_g2 = P::G2::generator();
+
+        for _ in 0..=max_degree_g2 {
+            public_parameter_group_2.push(elem_g2);
+            elem_g2 = elem_g2.mul(&s);
+        }
+
+        KZGCommitmentScheme {
+            public_parameter_group_1,
+            public_parameter_group_2,
+            max_degree,
+        }
+    }
+
+    /// Shrink this to only for verifier use, keeping the powers needed to
+    /// verify openings on cosets of `coset_size` points, such as PeerDAS
+    /// cells: the interpolation polynomials are committed to in G1 and the
+    /// vanishing polynomials `X^coset_size - h^coset_size` in G2.
+    pub fn shrink_to_coset_verifier_only(&self, coset_size: usize) -> KZGResult<Self> {
+        if coset_size == 0
+            || coset_size > self.public_parameter_group_1.len()
+            || coset_size >= self.public_parameter_group_2.len()
+        {
+            return Err(KZGError::DegreeError);
+        }
+        Ok(Self {
+            public_parameter_group_1: self.public_parameter_group_1[..coset_size].to_vec(),
+            public_parameter_group_2: self.public_parameter_group_2[..=coset_size].to_vec(),
+            max_degree: self.max_degree,
+        })
+    }
+
+    /// Serialize the parameters to unchecked bytes, followed by the max
+    /// degree.
+    pub fn to_unchecked_bytes(&self) -> KZGResult<Vec<u8>> {
+        let mut bytes = vec![];
+        let len_1 = self.public_parameter_group_1.len() as u32;
+        let len_2 = self.public_parameter_group_2.len() as u32;
+        bytes.extend(len_1.to_le_bytes());
+        bytes.extend(len_2.to_le_bytes());
+
+        for i in &self.public_parameter_group_1 {
+            let mut buf = Vec::new();
+            i.serialize_with_mode(&mut buf, Compress::No).unwrap();
+            bytes.extend(buf);
+        }
+        for i in &self.public_parameter_group_2 {
+            let mut buf = Vec::new();
+            i.serialize_with_mode(&mut buf, Compress::No).unwrap();
+            bytes.extend(buf);
+        }
+        bytes.extend((self.max_degree as u32).to_le_bytes());
+        Ok(bytes)
+    }
+
+    /// Deserialize the parameters from unchecked bytes. Without a trailing
+    /// max degree, it is the one of the powers in G1.
+    pub fn from_unchecked_bytes(bytes: &[u8]) -> KZGResult<Self> {
+        if bytes.len() < 8 {
+            return Err(KZGError::DeserializationError);
+        }
+        let mut len_1_bytes = [0u8; 4];
+        let mut len_2_bytes = [0u8; 4];
+        len_1_bytes.copy_from_slice(&bytes[0..4]);
+        len_2_bytes.copy_from_slice(&bytes[4..8]);
+        let len_1 = u32::from_le_bytes(len_1_bytes) as usize;
+        let len_2 = u32::from_le_bytes(len_2_bytes) as usize;
+        let n_1 = P::G1::default().serialized_size(Compress::No);
+        let n_2 = P::G2::default().serialized_size(Compress::No);
+
+        let bytes_1 = &bytes[8..];
+        let bytes_2 = &bytes[8 + (n_1 * len_1)..];
+        let mut p1 = vec![];
+        let mut p2 = vec![];
+
+        for i in 0..len_1 {
+            let reader = &bytes_1[n_1 * i..n_1 * (i + 1)];
+            let g1 = P::G1::deserialize_with_mode(reader, Compress::No, Validate::No)
+                .map_err(|_| KZGError::DeserializationError)?;
+            p1.push(g1);
+        }
+
+        for i in 0..len_2 {
+            let reader = &bytes_2[n_2 * i..n_2 * (i + 1)];
+            let g2 = P::G2::deserialize_with_mode(reader, Compress::No, Validate::No)
+                .map_err(|_| KZGError::DeserializationError)?;
+            p2.push(g2);
+        }
+
+        let rest = &bytes_2[n_2 * len_2..];
+        let max_degree = match rest.len() {
+            0 if len_1 > 0 => len_1 - 1,
+            4 => {
+                let mut max_degree_bytes = [0u8; 4];
+                max_degree_bytes.copy_from_slice(rest);
+                u32::from_le_bytes(max_degree_bytes) as usize
+            }
+            _ => return Err(KZGError::DeserializationError),
+        };
+        if len_1 == 0 || max_degree + 1 < len_1 {
+            return Err(KZGError::DeserializationError);
+        }
+
+        Ok(Self {
+            public_parameter_group_1: p1,
+            public_parameter_group_2: p2,
+            max_degree,
+        })
+    }
+
+    /// Precompute the FFTs of the SRS to open polynomials of up to
+    /// `poly_len` coefficients at every point of the domain of size
+    /// `domain_size`, both powers of two with `poly_len <= domain_size`.
+    pub fn precompute_open_all(&self, poly_len: usize, domain_size: usize) -> KZGResult<FK20<P>> {
+        FK20::new(self, poly_len, 1, domain_size)
+    }
+
+    /// Compute the opening proofs of `poly` at all the points `w^i` of the
+    /// domain with FK20, in `O(n log n)` group operations instead of the
+    /// `O(n^2)` of calling `prove` at each point. The `i`-th proof verifies
+    /// with `verify` at `precomputed.coset_shift(i)`.
+    pub fn open_all_on_domain(
+        &self,
+        precomputed: &FK20<P>,
+        poly: &FpPolynomial<P::ScalarField>,
+    ) -> KZGResult<Vec<KZGCommitment<P::G1>>> {
+        if precomputed.coset_size() != 1 {
+            return Err(KZGError::DomainError);
+        }
+        Ok(precomputed
+            .compute_proofs(poly)?
+            .into_iter()
+            .map(KZGCommitment)
+            .collect())
+    }
+
+    /// Absorb the degree bound statement and return the transcript.
+    fn degree_bound_transcript(
+        cm: &KZGCommitment<P::G1>,
+        degree: usize,
+        shifted_commitment: &P::G1,
+    ) -> Transcript {
+        let mut transcript = Transcript::new(b"kzg_degree_bound");
+        transcript.append_serializable(b"commitment", &cm.0);
+        transcript.append_message(b"degree", &(degree as u64).to_le_bytes());
+        transcript.append_serializable(b"shifted_commitment", shifted_commitment);
+        transcript
+    }
+
+    /// Prove that `poly` has degree at most `degree`, as in Marlin: commit to
+    /// the shifted polynomial `X^(D - degree) * poly`, where `D` is the max
+    /// degree of the SRS in G1, and open both polynomials at a random point
+    /// `z` with a single proof. A polynomial of larger degree would be
+    /// shifted beyond the powers in G1, so that it cannot be committed to.
+    pub fn prove_degree_bound(
+        &self,
+        poly: &FpPolynomial<P::ScalarField>,
+        degree: usize,
+    ) -> KZGResult<KZGDegreeBoundProof<P::G1>> {
+        if poly.degree() > degree || degree > self.max_degree {
+            return Err(KZGError::DegreeError);
+        }
+        let mut coefs = vec![P::ScalarField::zero(); self.max_degree - degree];
+        coefs.extend_from_slice(poly.get_coefs_ref());
+        let shifted = FpPolynomial::from_coefs(coefs);
+        let shifted_commitment = self.commit(&shifted)?.0;
+
+        let cm = self.commit(poly)?;
+        let mut transcript = Self::degree_bound_transcript(&cm, degree, &shifted_commitment);
+        let z: P::ScalarField = transcript.challenge_scalar(b"z");
+        let value = poly.eval(&z);
+        transcript.append_serializable(b"value", &value);
+        let gamma: P::ScalarField = transcript.challenge_scalar(b"gamma");
+
+        let combined = poly.add(&shifted.mul_scalar(&gamma));
+        let opening = self.prove(&combined, &z, self.max_degree)?.0;
+        Ok(KZGDegreeBoundProof {
+            shifted_commitment,
+            value,
+            opening,
+        })
+    }
+
+    /// Verify that the polynomial inside commitment has degree at most
+    /// `degree`: the shifted commitment `C'` must open to
+    /// `z^(D - degree) * v` where `C` opens to `v`, checked with a single
+    /// opening of `C + gamma * C'` at `z`.
+    pub fn verify_degree_bound(
+        &self,
+        cm: &KZGCommitment<P::G1>,
+        degree: usize,
+        proof: &KZGDegreeBoundProof<P::G1>,
+    ) -> KZGResult<()> {
+        if degree > self.max_degree {
+            return Err(KZGError::DegreeError);
+        }
+        let mut transcript = Self::degree_bound_transcript(cm, degree, &proof.shifted_commitment);
+        let z: P::ScalarField = transcript.challenge_scalar(b"z");
+        transcript.append_serializable(b"value", &proof.value);
+        let gamma: P::ScalarField = transcript.challenge_scalar(b"gamma");
+
+        let shifted_value = z.pow([(self.max_degree - degree) as u64]) * proof.value;
+        let combined = cm.0 + proof.shifted_commitment * gamma;
+        self.verify(
+            &KZGCommitment(combined),
+            self.max_degree,
+            &z,
+            &(proof.value + gamma * shifted_value),
+            &KZGCommitment(proof.opening),
+        )
+        .map_err(|_| KZGError::DegreeError)
+    }
+
+    /// Prove the claims on the polynomials `polys` with a single proof: the
+    /// claims are combined with the powers of a challenge `r` into the
+    /// polynomial `h = sum_i r^i (f_i - y_i) / (X - z_i)`, whose commitment
+    /// fixes a challenge `t`, and `sum_i r^i (f_i - y_i) / (t - z_i) - h` is
+    /// opened to zero at `t`. The quotients of the claims at the same point
+    /// are computed with a single division.
+    pub fn prove_multi_point(
+        &self,
+        transcript: &mut Transcript,
+        polys: &[FpPolynomial<P::ScalarField>],
+        claims: &[KZGOpeningClaim<P>],
+    ) -> KZGResult<KZGMultiPointProof<P::G1>> {
+        if claims.is_empty() || polys.len() != claims.len() {
+            return Err(KZGError::IndexError);
+        }
+        for claim in claims {
+            claim.append_to_transcript(transcript);
+        }
+        let r: P::ScalarField = transcript.challenge_scalar(b"r");
+
+        // sum_i r^i (f_i - y_i) for each distinct point z_i.
+        let mut numerators: Vec<(P::ScalarField, FpPolynomial<P::ScalarField>)> = vec![];
+        let mut power = P::ScalarField::one();
+        for (poly, claim) in polys.iter().zip(claims) {
+            let term = poly
+                .sub(&FpPolynomial::from_coefs(vec![claim.value]))
+                .mul_scalar(&power);
+            match numerators.iter_mut().find(|(z, _)| *z == claim.point) {
+                Some((_, numerator)) => numerator.add_assign(&term),
+                None => numerators.push((claim.point, term)),
+            }
+            power *= r;
+        }
+
+        let mut quotient = FpPolynomial::zero();
+        for (z, numerator) in &numerators {
+            let (q, rem) = numerator.div_rem(&FpPolynomial::from_coefs(vec![
+                z.neg(),
+                P::ScalarField::one(),
+            ]));
+            if !rem.is_zero() {
+                return Err(KZGError::PCSProveEvalError);
+            }
+            quotient.add_assign(&q);
+        }
+        let quotient_commitment = self.commit(&quotient)?.0;
+        transcript.append_serializable(b"quotient", &quotient_commitment);
+        let t: P::ScalarField = transcript.challenge_scalar(b"t");
+
+        let mut linearised = quotient.neg();
+        for (z, numerator) in &numerators {
+            let inverse = (t - z).inverse().ok_or(KZGError::PCSProveEvalError)?;
+            linearised.add_assign(&numerator.mul_scalar(&inverse));
+        }
+        let opening = self.prove(&linearised, &t, linearised.degree())?.0;
+        Ok(KZGMultiPointProof {
+            quotient: quotient_commitment,
+            opening,
+        })
+    }
+
+    /// Verify a proof of `prove_multi_point` with the powers `[1]_2` and
+    /// `[tau]_2`: `sum_i r^i / (t - z_i) (C_i - [y_i]_1) - [h]_1` must open
+    /// to zero at `t`.
+    pub fn verify_multi_point(
+        &self,
+        transcript: &mut Transcript,
+        claims: &[KZGOpeningClaim<P>],
+        proof: &KZGMultiPointProof<P::G1>,
+    ) -> KZGResult<()> {
+        if claims.is_empty() {
+            return Err(KZGError::IndexError);
+        }
+        for claim in claims {
+            claim.append_to_transcript(transcript);
+        }
+        let r: P::ScalarField = transcript.challenge_scalar(b"r");
+        transcript.append_serializable(b"quotient", &proof.quotient);
+        let t: P::ScalarField = transcript.challenge_scalar(b"t");
+
+        let mut scalars: Vec<P::ScalarField> = claims.iter().map(|claim| t - claim.point).collect();
+        if scalars.iter().any(|s| s.is_zero()) {
+            return Err(KZGError::PCSProveEvalError);
+        }
+        batch_inversion(&mut scalars);
+        let mut power = P::ScalarField::one();
+        let mut value = P::ScalarField::zero();
+        for (scalar, claim) in scalars.iter_mut().zip(claims) {
+            *scalar *= power;
+            value += *scalar * claim.value;
+            power *= r;
+        }
+        let mut bases: Vec<P::G1> = claims.iter().map(|claim| claim.commitment.0).collect();
+        bases.push(proof.quotient);
+        scalars.push(P::ScalarField::one().neg());
+        let combined = P::G1::msm(&P::G1::normalize_batch(&bases), &scalars).unwrap();
+
+        self.verify(
+            &KZGCommitment(combined - self.public_parameter_group_1[0] * value),
+            self.max_degree,
+            &t,
+            &P::ScalarField::zero(),
+            &KZGCommitment(proof.opening),
+        )
+    }
+}
+
+/// KZG commitment scheme over the BN254 curve
+pub type KZGCommitmentSchemeBN254 = KZGCommitmentScheme<Bn254>;
+
+/// KZG commitment scheme over the BLS12-381 curve
+pub type KZGCommitmentSchemeBLS12_381 = KZGCommitmentScheme<Bls12_381>;
+
+impl<P: Pairing> PolyComScheme for KZGCommitmentScheme<P> {
+    type Field = P::ScalarField;
+    type Commitment = KZGCommitment<P::G1>;
+    type Proof = KZGCommitment<P::G1>;
+
+    fn max_degree(&self) -> usize {
+        self.max_degree
+    }
+
+    fn commit(&self, polynomial: &FpPolynomial<Self::Field>) -> KZGResult<Self::Commitment> {
+        let coefs = polynomial.get_coefs_ref();
+
+        let degree = polynomial.degree();
+
+        if degree + 1 > self.public_parameter_group_1.len() {
+            return Err(KZGError::DegreeError);
+        }
+
+        let points_raw = P::G1::normalize_batch(&self.public_parameter_group_1[0..degree + 1]);
+
+        let commitment_value = P::G1::msm(&points_raw, coefs).unwrap();
+
+        Ok(KZGCommitment(commitment_value))
+    }
+
+    fn eval(&self, poly: &FpPolynomial<Self::Field>, point: &Self::Field) -> Self::Field {
+        poly.eval(point)
+    }
+
+    fn prove(
+        &self,
+        poly: &FpPolynomial<Self::Field>,
+        x: &Self::Field,
+        max_degree: usize,
+    ) -> KZGResult<Self::Proof> {
+        let eval = poly.eval(x);
+
+        if poly.degree() > max_degree {
+            return Err(KZGError::DegreeError);
+        }
+
+        let nominator = poly.sub(&FpPolynomial::from_coefs(vec![eval]));
+
+        // Negation must happen in Fq
+        let point_neg = x.neg();
+
+        // X - x
+        let vanishing_poly = FpPolynomial::from_coefs(vec![point_neg, Self::Field::one()]);
+        let (q_poly, r_poly) = nominator.div_rem(&vanishing_poly); // P(X)-P(x) / (X-x)
+
+        if !r_poly.is_zero() {
+            return Err(KZGError::PCSProveEvalError);
+        }
+
+        let proof = self.commit(&q_poly).unwrap();
+        Ok(proof)
+    }
+
+    fn verify(
+        &self,
+        cm: &Self::Commitment,
+        _degree: usize,
+        point: &Self::Field,
+        eval: &Self::Field,
+        proof: &Self::Proof,
+    ) -> KZGResult<()> {
+        let g1_0 = self.public_parameter_group_1[0];
+        let g2_0 = self.public_parameter_group_2[0];
+        let g2_1 = self.public_parameter_group_2[1];
+
+        let x_minus_point_group_element_group_2 = &g2_1.sub(&g2_0.mul(point));
+
+        let left_pairing_eval = if eval.is_zero() {
+            P::pairing(cm.0, g2_0)
+        } else {
+            P::pairing(cm.0.sub(&g1_0.mul(eval)), g2_0)
+        };
+
+        let right_pairing_eval = P::pairing(proof.0, x_minus_point_group_element_group_2);
+
+        if left_pairing_eval == right_pairing_eval {
+            Ok(())
+        } else {
+            Err(KZGError::PCSProveEvalError)
+        }
+    }
+
+    fn shrink_to_verifier_only(&self) -> KZGResult<Self> {
+        Ok(Self {
+            public_parameter_group_1: vec![self.public_parameter_group_1[0]],
+            public_parameter_group_2: vec![
+                self.public_parameter_group_2[0],
+                self.public_parameter_group_2[1],
+            ],
+            max_degree: self.max_degree,
+        })
+    }
+}
+
+impl<P: Pairing> HomomorphicPolyComScheme for KZGCommitmentScheme<P> {
+    fn apply_blind_factors(
+        &self,
+        commitment: &Self::Commitment,
+        blinds: &[Self::Field],
+        zeroing_degree: usize,
+    ) -> Self::Commitment {
+        let mut commitment = commitment.0;
+        for (i, blind) in blinds.iter().enumerate() {
+            let mut blind = *blind;
+            commitment += &(self.public_parameter_group_1[i] * blind);
+            blind = blind.neg();
+            commitment += &(self.public_parameter_group_1[zeroing_degree + i] * blind);
+        }
+        KZGCommitment(commitment)
+    }
+}
+
+#[cfg(test)]
+mod tests_kzg_impl {
+    use ark_bn254::{Fr, G1Projective};
+    use ark_std::test_rng;
+
+    use super::*;
+
+    fn check_public_parameters_generation<P: Pairing>() {
+        let param_size = 5;
+        let mut prng = test_rng();
+        let kzg_scheme = KZGCommitmentScheme::<P>::new(param_size, &mut prng);
+        let g1_power1 = kzg_scheme.public_parameter_group_1[1].clone();
+        let g2_power1 = kzg_scheme.public_parameter_group_2[1].clone();
+
+        // Check parameters for G1
+        for i in 0..param_size - 1 {
+            let elem_first_group_1 = kzg_scheme.public_parameter_group_1[i].clone();
+            let elem_next_group_1 = kzg_scheme.public_parameter_group_1[i + 1].clone();
+            let elem_next_group_1_target = P::pairing(&elem_next_group_1, &P::G2::generator());
+            let elem_next_group_1_target_recomputed = P::pairing(&elem_first_group_1, &g2_power1);
+            assert_eq!(
+                elem_next_group_1_target_recomputed,
+                elem_next_group_1_target
+            );
+        }
+
+        // Check parameters for G2
+        let elem_first_group_2 = kzg_scheme.public_parameter_group_2[0].clone();
+        let elem_second_group_2 = kzg_scheme.public_parameter_group_2[1].clone();
+        let elem_next_group_2_target = P::pairing(&P::G1::generator(), &elem_second_group_2);
+        let elem_next_group_2_target_recomputed = P::pairing(&g1_power1, &elem_first_group_2);
+
+        assert_eq!(
+            elem_next_group_2_target_recomputed,
+            elem_next_group_2_target
+        );
+    }
+
+    // Check the size of the KZG being generated.
+    fn generation_of_crs<P: Pairing>() {
+        let n = 1 << 5;
+        let mut prng = test_rng();
+        let kzg_scheme = KZGCommitmentScheme::<P>::new(n, &mut prng);
+        assert_eq!(kzg_scheme.public_parameter_group_1.len(), n + 1);
+        assert_eq!(kzg_scheme.public_parameter_group_2.len(), 2);
+    }
+
+    #[test]
+    fn test_homomorphic_poly_com_elem() {
+        let mut prng = test_rng();
+        let pcs = KZGCommitmentSchemeBN254::new(20, &mut prng);
+        type Field = Fr;
+        let one = Field::one();
+        let two = one.add(&one);
+        let three = two.add(&one);
+        let four = three.add(&one);
+        let six = three.add(&three);
+        let eight = six.add(&two);
+        let poly1 = FpPolynomial::from_coefs(vec![two, three, six]);
+
+        let commitment1 = pcs.commit(&poly1).unwrap();
+
+        let poly2 = FpPolynomial::from_coefs(vec![one, eight, four]);
+
+        let commitment2 = pcs.commit(&poly2).unwrap();
+
+        // Add two polynomials
+        let poly_sum = poly1.add(&poly2);
+        let commitment_sum = pcs.commit(&poly_sum).unwrap();
+        let commitment_sum_computed = commitment1.add(&commitment2);
+        assert_eq!(commitment_sum, commitment_sum_computed);
+
+        // Multiplying all the coefficients of a polynomial by some value
+        let exponent = four.add(&one);
+        let poly1_mult_5 = poly1.mul_scalar(&exponent);
+        let commitment_poly1_mult_5 = pcs.commit(&poly1_mult_5).unwrap();
+        let commitment_poly1_mult_5_hom = commitment1.mul(&exponent);
+        assert_eq!(commitment_poly1_mult_5, commitment_poly1_mult_5_hom);
+    }
+
+    #[test]
+    fn test_public_parameters() {
+        check_public_parameters_generation::<Bn254>();
+    }
+
+    #[test]
+    fn test_generation_of_crs() {
+        generation_of_crs::<Bn254>();
+    }
+
+    #[test]
+    fn test_commit() {
+        let mut prng = test_rng();
+        let pcs = KZGCommitmentSchemeBN254::new(10, &mut prng);
+        type Field = Fr;
+        let one = Field::one();
+        let two = one.add(&one);
+        let three = two.add(&one);
+        let six = three.add(&three);
+
+        let fq_poly = FpPolynomial::from_coefs(vec![two, three, six]);
+        let commitment = pcs.commit(&fq_poly).unwrap();
+
+        let coefs_poly_scalar: Vec<_> = fq_poly.get_coefs_ref().iter().cloned().collect();
+        let mut expected_committed_value = G1Projective::zero();
+
+        // Doing the multiexp by hand
+        for (i, coef) in coefs_poly_scalar.iter().enumerate() {
+            let g_i = pcs.public_parameter_group_1[i].clone();
+            expected_committed_value = expected_committed_value.add(&g_i.mul(coef));
+        }
+        assert_eq!(expected_committed_value, commitment.0);
+    }
+
+    #[test]
+    fn test_eval() {
+        let mut prng = test_rng();
+        let pcs = KZGCommitmentSchemeBN254::new(10, &mut prng);
+        type Field = Fr;
+        let one = Field::one();
+        let two = one.add(&one);
+        let three = two.add(&one);
+        let four = three.add(&one);
+        let six = three.add(&three);
+        let seven = six.add(&one);
+        let fq_poly = FpPolynomial::from_coefs(vec![one, two, four]);
+        let point = one;
+        let max_degree = fq_poly.degree();
+
+        let degree = fq_poly.degree();
+        let commitment_value = pcs.commit(&fq_poly).unwrap();
+
+        // Check that an error is returned if the degree of the polynomial exceeds the maximum degree.
+        let wrong_max_degree = 1;
+        let res = pcs.prove(&fq_poly, &point, wrong_max_degree);
+        assert!(res.is_err());
+
+        let proof = pcs.prove(&fq_poly, &point, max_degree).unwrap();
+
+        pcs.verify(&commitment_value, degree, &point, &seven, &proof)
+            .unwrap();
+
+        let new_pcs = pcs.shrink_to_verifier_only().unwrap();
+        new_pcs
+            .verify(&commitment_value, degree, &point, &seven, &proof)
+            .unwrap();
+
+        let wrong_eval = one;
+        let res = pcs.verify(&commitment_value, degree, &point, &wrong_eval, &proof);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_shrink_to_verifier_only() {
+        let mut prng = test_rng();
+        let pcs = KZGCommitmentSchemeBN254::new_with_g2_degree(15, 8, &mut prng);
+        let verifier = pcs.shrink_to_verifier_only().unwrap();
+        assert_eq!(verifier.public_parameter_group_1.len(), 1);
+        assert_eq!(verifier.public_parameter_group_2.len(), 2);
+        assert_eq!(verifier.max_degree(), 15);
+
+        let verifier = pcs.shrink_to_coset_verifier_only(4).unwrap();
+        assert_eq!(verifier.public_parameter_group_1.len(), 4);
+        assert_eq!(verifier.public_parameter_group_2.len(), 5);
+        assert!(pcs.shrink_to_coset_verifier_only(0).is_err());
+        assert!(pcs.shrink_to_coset_verifier_only(9).is_err());
+    }
+
+    #[test]
+    fn test_open_all_on_domain() {
+        let mut prng = test_rng();
+        let pcs = KZGCommitmentSchemeBN254::new(15, &mut prng);
+        let poly = FpPolynomial::random(&mut prng, 15);
+        let commitment = pcs.commit(&poly).unwrap();
+        for domain_size in [16, 32] {
+            let precomputed = pcs.precompute_open_all(16, domain_size).unwrap();
+            let proofs = pcs.open_all_on_domain(&precomputed, &poly).unwrap();
+            assert_eq!(proofs.len(), domain_size);
+            for (i, proof) in proofs.iter().enumerate() {
+                let point = precomputed.coset_shift(i);
+                assert_eq!(*proof, pcs.prove(&poly, &point, 15).unwrap());
+                pcs.verify(&commitment, 15, &point, &poly.eval(&point), proof)
+                    .unwrap();
+            }
+        }
+
+        // The domain must contain as many points as coefficients.
+        assert!(pcs.precompute_open_all(16, 8).is_err());
+        let precomputed = pcs.precompute_open_all(8, 8).unwrap();
+        assert!(pcs.open_all_on_domain(&precomputed, &poly).is_err());
+    }
+
+    #[test]
+    fn test_degree_bound() {
+        let mut prng = test_rng();
+        let pcs = KZGCommitmentSchemeBN254::new(16, &mut prng);
+        let verifier = pcs.shrink_to_verifier_only().unwrap();
+        let poly = FpPolynomial::random(&mut prng, 10);
+        let commitment = pcs.commit(&poly).unwrap();
+
+        for degree in [10, 12, 16] {
+            let proof = pcs.prove_degree_bound(&poly, degree).unwrap();
+            verifier
+                .verify_degree_bound(&commitment, degree, &proof)
+                .unwrap();
+            // The proof does not hold for a smaller bound.
+            assert!(verifier
+                .verify_degree_bound(&commitment, degree - 1, &proof)
+                .is_err());
+            let mut wrong_proof = proof.clone();
+            wrong_proof.value += Fr::one();
+            assert!(verifier
+                .verify_degree_bound(&commitment, degree, &wrong_proof)
+                .is_err());
+        }
+        assert!(pcs.prove_degree_bound(&poly, 9).is_err());
+        assert!(pcs.prove_degree_bound(&poly, 17).is_err());
+
+        // A proof for another polynomial does not hold.
+        let other = FpPolynomial::random(&mut prng, 10);
+        let proof = pcs.prove_degree_bound(&other, 10).unwrap();
+        assert!(verifier
+            .verify_degree_bound(&commitment, 10, &proof)
+            .is_err());
+
+        // Shifting a polynomial of larger degree exceeds the SRS in G1.
+        let poly = FpPolynomial::random(&mut prng, 11);
+        let mut coefs = vec![Fr::zero(); 6];
+        coefs.extend_from_slice(poly.get_coefs_ref());
+        assert!(pcs.commit(&FpPolynomial::from_coefs(coefs)).is_err());
+    }
+
+    #[test]
+    fn test_unchecked_bytes() {
+        let mut prng = test_rng();
+        let pcs = KZGCommitmentSchemeBN254::new_with_g2_degree(15, 4, &mut prng);
+        for pcs in [pcs.clone(), pcs.shrink_to_verifier_only().unwrap()] {
+            let bytes = pcs.to_unchecked_bytes().unwrap();
+            assert_eq!(
+                KZGCommitmentSchemeBN254::from_unchecked_bytes(&bytes).unwrap(),
+                pcs
+            );
+
+            // Without the max degree, it is the one of the powers in G1.
+            let bytes = &bytes[..bytes.len() - 4];
+            let decoded = KZGCommitmentSchemeBN254::from_unchecked_bytes(bytes).unwrap();
+            assert_eq!(decoded.max_degree(), pcs.public_parameter_group_1.len() - 1);
+        }
+    }
+}
@@ -1,2 +1,7 @@
+pub mod fri;
+pub mod hiding_kzg;
+pub mod ipa;
 // #[cfg(feature = "arkworks")]
-mod arkworks_bn254;
+pub mod kzg;
+pub mod multilinear_kzg;
+pub mod zeromorph;
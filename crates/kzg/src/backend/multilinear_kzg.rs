@@ -0,0 +1,231 @@
+//! The multilinear KZG commitment scheme of Papamanthou, Shi and Tamassia
+//! ("Signatures of correct computation", PST13).
+//!
+//! A polynomial is committed to as `[f(t)]_1` for a secret point `t`, using
+//! the Lagrange basis of the boolean hypercube `[eq(b, t)]_1`. An opening at
+//! `z` uses the decomposition `f(X) - f(z) = sum_i (X_i - z_i) * q_i(X)`,
+//! where `q_i` only depends on the variables after `X_i`, and is checked with
+//! one pairing per variable.
+
+use ark_ec::{pairing::Pairing, CurveGroup, VariableBaseMSM};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{ops::*, Zero};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    backend::kzg::KZGCommitment,
+    errs::{KZGError, KZGResult},
+    primitives::{
+        multilinear::DenseMultilinearPolynomial,
+        serde::{ark_deserialize, ark_serialize},
+    },
+    MultilinearPolyComScheme,
+};
+
+/// Multilinear KZG opening proof, the commitments to the quotients `q_i`.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct MultilinearKZGProof<G1: CanonicalSerialize + CanonicalDeserialize>(
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")] pub Vec<G1>,
+);
+
+/// Multilinear KZG commitment scheme about `PairingEngine`.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct MultilinearKZGScheme<P: Pairing> {
+    /// public parameter about G1: for each `m`, the Lagrange basis
+    /// `[eq(b, (t_{n-m}, ..., t_{n-1}))]_1` over the last `m` variables.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub public_parameter_group_1: Vec<Vec<P::G1>>,
+    /// public parameter about G2: `[1]_2` followed by the `[t_i]_2`.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub public_parameter_group_2: Vec<P::G2>,
+}
+
+impl<P: Pairing> MultilinearKZGScheme<P> {
+    /// Create a new instance of a multilinear KZG commitment scheme.
+    /// `max_num_vars` - max number of variables of the polynomial,
+    /// `prng` - pseudo-random generator.
+    /// WARN only for testing purposes.
+    #[cfg(test)]
+    pub fn new<R: ark_std::rand::RngCore>(
+        max_num_vars: usize,
+        prng: &mut R,
+    ) -> MultilinearKZGScheme<P> {
+        use ark_ec::PrimeGroup;
+        use ark_ff::One;
+        use ark_std::UniformRand;
+        let t: Vec<P::ScalarField> = (0..max_num_vars)
+            .map(|_| P::ScalarField::rand(prng))
+            .collect();
+
+        let g1 = P::G1::generator();
+        let mut eq = vec![P::ScalarField::one()];
+        let mut public_parameter_group_1 = vec![vec![g1]];
+        for t_i in t.iter().rev() {
+            // Add `t_i` as the new first variable.
+            eq = eq
+                .iter()
+                .flat_map(|e| [*e * (P::ScalarField::one() - t_i), *e * t_i])
+                .collect();
+            public_parameter_group_1.push(eq.iter().map(|e| g1.mul(e)).collect());
+        }
+
+        let g2 = P::G2::generator();
+        let mut public_parameter_group_2 = vec![g2];
+        public_parameter_group_2.extend(t.iter().map(|t_i| g2.mul(t_i)));
+
+        MultilinearKZGScheme {
+            public_parameter_group_1,
+            public_parameter_group_2,
+        }
+    }
+
+    fn msm(bases: &[P::G1], scalars: &[P::ScalarField]) -> P::G1 {
+        P::G1::msm(&P::G1::normalize_batch(bases), scalars).unwrap()
+    }
+}
+
+impl<P: Pairing> MultilinearPolyComScheme for MultilinearKZGScheme<P> {
+    type Field = P::ScalarField;
+    type Commitment = KZGCommitment<P::G1>;
+    type Proof = MultilinearKZGProof<P::G1>;
+
+    fn max_num_vars(&self) -> usize {
+        self.public_parameter_group_2.len() - 1
+    }
+
+    fn commit(
+        &self,
+        polynomial: &DenseMultilinearPolynomial<Self::Field>,
+    ) -> KZGResult<Self::Commitment> {
+        let basis = self
+            .public_parameter_group_1
+            .get(polynomial.num_vars())
+            .ok_or(KZGError::DegreeError)?;
+        Ok(KZGCommitment(Self::msm(basis, polynomial.get_evals_ref())))
+    }
+
+    fn eval(
+        &self,
+        polynomial: &DenseMultilinearPolynomial<Self::Field>,
+        point: &[Self::Field],
+    ) -> Self::Field {
+        polynomial.evaluate(point)
+    }
+
+    fn prove(
+        &self,
+        polynomial: &DenseMultilinearPolynomial<Self::Field>,
+        point: &[Self::Field],
+    ) -> KZGResult<Self::Proof> {
+        let num_vars = polynomial.num_vars();
+        if point.len() != num_vars {
+            return Err(KZGError::PCSProveEvalError);
+        }
+        if num_vars >= self.public_parameter_group_1.len() {
+            return Err(KZGError::DegreeError);
+        }
+
+        let mut evals = polynomial.get_evals_ref().to_vec();
+        let mut proof = Vec::with_capacity(num_vars);
+        for (i, z) in point.iter().enumerate() {
+            let half = evals.len() / 2;
+            // q_i = f_i(1, X_{i+1}, ...) - f_i(0, X_{i+1}, ...)
+            let quotient: Vec<Self::Field> =
+                (0..half).map(|j| evals[2 * j + 1] - evals[2 * j]).collect();
+            proof.push(Self::msm(
+                &self.public_parameter_group_1[num_vars - i - 1],
+                &quotient,
+            ));
+            // f_{i+1} = f_i(z_i, X_{i+1}, ...)
+            for (j, q) in quotient.iter().enumerate() {
+                evals[j] = evals[2 * j] + *z * q;
+            }
+            evals.truncate(half);
+        }
+        Ok(MultilinearKZGProof(proof))
+    }
+
+    fn verify(
+        &self,
+        cm: &Self::Commitment,
+        point: &[Self::Field],
+        eval: &Self::Field,
+        proof: &Self::Proof,
+    ) -> KZGResult<()> {
+        let max_num_vars = self.max_num_vars();
+        if point.len() > max_num_vars || proof.0.len() != point.len() {
+            return Err(KZGError::PCSProveEvalError);
+        }
+        let offset = max_num_vars - point.len();
+        let g1_0 = self.public_parameter_group_1[0][0];
+        let g2_0 = self.public_parameter_group_2[0];
+
+        // e(C - [v]_1, [1]_2) = prod_i e(pi_i, [t_i - z_i]_2)
+        let mut left = vec![cm.0.sub(&g1_0.mul(eval))];
+        let mut right = vec![g2_0];
+        for (i, (z, pi)) in point.iter().zip(&proof.0).enumerate() {
+            left.push(pi.neg());
+            right.push(self.public_parameter_group_2[1 + offset + i].sub(&g2_0.mul(z)));
+        }
+
+        if P::multi_pairing(left, right).is_zero() {
+            Ok(())
+        } else {
+            Err(KZGError::PCSProveEvalError)
+        }
+    }
+
+    fn shrink_to_verifier_only(&self) -> KZGResult<Self> {
+        Ok(Self {
+            public_parameter_group_1: vec![self.public_parameter_group_1[0].clone()],
+            public_parameter_group_2: self.public_parameter_group_2.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests_multilinear_kzg {
+    use super::MultilinearKZGScheme;
+    use crate::{primitives::multilinear::DenseMultilinearPolynomial, MultilinearPolyComScheme};
+    use ark_bls12_381::Bls12_381;
+    use ark_bn254::Bn254;
+    use ark_ec::pairing::Pairing;
+    use ark_ff::{One, UniformRand};
+    use ark_std::test_rng;
+
+    fn check_pst13<P: Pairing>(max_num_vars: usize) {
+        let mut prng = test_rng();
+        let pcs = MultilinearKZGScheme::<P>::new(max_num_vars, &mut prng);
+        let verifier = pcs.shrink_to_verifier_only().unwrap();
+        assert_eq!(verifier.max_num_vars(), max_num_vars);
+
+        for num_vars in 0..=max_num_vars {
+            let poly = DenseMultilinearPolynomial::random(&mut prng, num_vars);
+            let point: Vec<P::ScalarField> = (0..num_vars)
+                .map(|_| P::ScalarField::rand(&mut prng))
+                .collect();
+            let com = pcs.commit(&poly).unwrap();
+            let eval = pcs.eval(&poly, &point);
+            let proof = pcs.prove(&poly, &point).unwrap();
+            assert_eq!(proof.0.len(), num_vars);
+            assert!(verifier.verify(&com, &point, &eval, &proof).is_ok());
+
+            let wrong_eval = eval + P::ScalarField::one();
+            assert!(verifier.verify(&com, &point, &wrong_eval, &proof).is_err());
+            if num_vars > 0 {
+                let mut wrong_point = point.clone();
+                wrong_point[0] += P::ScalarField::one();
+                assert!(verifier.verify(&com, &wrong_point, &eval, &proof).is_err());
+            }
+        }
+
+        let poly = DenseMultilinearPolynomial::random(&mut prng, max_num_vars + 1);
+        assert!(pcs.commit(&poly).is_err());
+    }
+
+    #[test]
+    fn test_pst13() {
+        check_pst13::<Bn254>(4);
+        check_pst13::<Bls12_381>(3);
+    }
+}
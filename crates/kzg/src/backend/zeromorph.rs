@@ -0,0 +1,326 @@
+//! Multilinear commitments from univariate KZG with Zeromorph (Kohrita and
+//! Towa, "Zeromorph: Zero-knowledge multilinear-evaluation proofs from
+//! homomorphic univariate commitments").
+//!
+//! A multilinear polynomial `f` in `n` variables is committed to as the
+//! univariate polynomial `U_n(f)(X) = sum_i f(i) X^i` of its evaluations on
+//! the hypercube. Writing `f(X) - v = sum_k (X_k - u_k) * q_k(X_0, ..., X_{k-1})`,
+//! the evaluation `f(u) = v` is equivalent to the univariate identity
+//!
+//! `U_n(f) - v * Phi_n(X) = sum_k (X^(2^k) * Phi_(n-k-1)(X^(2^(k+1))) - u_k * Phi_(n-k)(X^(2^k))) * U_k(q_k)`
+//!
+//! with `Phi_m(X) = sum_(i < 2^m) X^i`, which is checked at a random point
+//! with a single KZG opening. The degree bounds `deg U_k(q_k) < 2^k` are
+//! enforced by committing to `sum_k y^k X^(D + 1 - 2^k) U_k(q_k)`, where `D`
+//! is the maximal degree of the SRS.
+
+use ark_ec::pairing::Pairing;
+use ark_ff::{Field, One, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::ops::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    backend::kzg::{KZGCommitment, KZGCommitmentScheme},
+    errs::{KZGError, KZGResult},
+    primitives::{
+        multilinear::DenseMultilinearPolynomial,
+        poly::FpPolynomial,
+        serde::{ark_deserialize, ark_serialize},
+        transcript::Transcript,
+    },
+    MultilinearPolyComScheme, PolyComScheme,
+};
+
+/// Zeromorph evaluation proof.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct ZeromorphProof<G1: CanonicalSerialize + CanonicalDeserialize> {
+    /// The commitments to the quotients `U_k(q_k)`.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub quotient_commitments: Vec<G1>,
+    /// The commitment to the degree-shifted batched quotient.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub batched_quotient_commitment: G1,
+    /// The KZG proof that the combined identity vanishes at the challenge.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub opening_proof: G1,
+}
+
+/// Zeromorph multilinear commitment scheme over a univariate KZG SRS.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ZeromorphScheme<P: Pairing> {
+    pcs: KZGCommitmentScheme<P>,
+    /// The maximal degree `D` of the SRS, kept by the verifier.
+    max_degree: usize,
+}
+
+/// `Phi_m(a) = sum_(i < 2^m) a^i = prod_(j < m) (1 + a^(2^j))`.
+fn phi<F: Field>(mut a: F, m: usize) -> F {
+    let mut res = F::one();
+    for _ in 0..m {
+        res *= F::one() + a;
+        a.square_in_place();
+    }
+    res
+}
+
+impl<P: Pairing> ZeromorphScheme<P> {
+    /// Use the SRS of `pcs`, supporting polynomials in up to
+    /// `log2(pcs.max_degree() + 1)` variables.
+    pub fn new(pcs: KZGCommitmentScheme<P>) -> Self {
+        let max_degree = pcs.max_degree();
+        Self { pcs, max_degree }
+    }
+
+    /// Return the underlying univariate scheme.
+    pub fn univariate_scheme(&self) -> &KZGCommitmentScheme<P> {
+        &self.pcs
+    }
+
+    /// Absorb the statement and return the transcript.
+    fn transcript(
+        commitment: &KZGCommitment<P::G1>,
+        point: &[P::ScalarField],
+        value: &P::ScalarField,
+    ) -> Transcript {
+        let mut transcript = Transcript::new(b"zeromorph");
+        transcript.append_serializable(b"commitment", &commitment.0);
+        for u in point {
+            transcript.append_serializable(b"point", u);
+        }
+        transcript.append_serializable(b"value", value);
+        transcript
+    }
+
+    /// Return the coefficients of the `U_k(q_k)` in the univariate identity
+    /// evaluated at `x`, and `Phi_n(x)`.
+    fn identity_coefficients(
+        x: &P::ScalarField,
+        point: &[P::ScalarField],
+    ) -> (Vec<P::ScalarField>, P::ScalarField) {
+        let n = point.len();
+        // x^(2^k) for k = 0..=n
+        let mut x_powers = vec![*x];
+        for k in 0..n {
+            x_powers.push(x_powers[k].square());
+        }
+        let coefs = point
+            .iter()
+            .enumerate()
+            .map(|(k, u)| {
+                x_powers[k] * phi(x_powers[k + 1], n - k - 1) - *u * phi(x_powers[k], n - k)
+            })
+            .collect();
+        (coefs, phi(*x, n))
+    }
+}
+
+impl<P: Pairing> MultilinearPolyComScheme for ZeromorphScheme<P> {
+    type Field = P::ScalarField;
+    type Commitment = KZGCommitment<P::G1>;
+    type Proof = ZeromorphProof<P::G1>;
+
+    fn max_num_vars(&self) -> usize {
+        ((self.max_degree + 1).ilog2()) as usize
+    }
+
+    fn commit(
+        &self,
+        polynomial: &DenseMultilinearPolynomial<Self::Field>,
+    ) -> KZGResult<Self::Commitment> {
+        self.pcs.commit(&FpPolynomial::from_coefs(
+            polynomial.get_evals_ref().to_vec(),
+        ))
+    }
+
+    fn eval(
+        &self,
+        polynomial: &DenseMultilinearPolynomial<Self::Field>,
+        point: &[Self::Field],
+    ) -> Self::Field {
+        polynomial.evaluate(point)
+    }
+
+    fn prove(
+        &self,
+        polynomial: &DenseMultilinearPolynomial<Self::Field>,
+        point: &[Self::Field],
+    ) -> KZGResult<Self::Proof> {
+        let n = polynomial.num_vars();
+        if point.len() != n {
+            return Err(KZGError::PCSProveEvalError);
+        }
+        if n > self.max_num_vars() {
+            return Err(KZGError::DegreeError);
+        }
+        let f_hat = FpPolynomial::from_coefs(polynomial.get_evals_ref().to_vec());
+        let commitment = self.pcs.commit(&f_hat)?;
+
+        // Fix the variables from the last one: q_k = g(.., 1) - g(.., 0).
+        let mut evals = polynomial.get_evals_ref().to_vec();
+        let mut quotients = vec![vec![]; n];
+        for k in (0..n).rev() {
+            let half = 1 << k;
+            let q: Vec<Self::Field> = (0..half).map(|j| evals[half + j] - evals[j]).collect();
+            for (e, q) in evals.iter_mut().zip(&q) {
+                *e += point[k] * q;
+            }
+            evals.truncate(half);
+            quotients[k] = q;
+        }
+        let value = evals[0];
+
+        let mut transcript = Self::transcript(&commitment, point, &value);
+        let quotient_polys: Vec<FpPolynomial<Self::Field>> = quotients
+            .into_iter()
+            .map(FpPolynomial::from_coefs)
+            .collect();
+        let mut quotient_commitments = Vec::with_capacity(n);
+        for q_hat in quotient_polys.iter() {
+            let c = self.pcs.commit(q_hat)?.0;
+            transcript.append_serializable(b"quotient", &c);
+            quotient_commitments.push(c);
+        }
+        let y: Self::Field = transcript.challenge_scalar(b"y");
+
+        // sum_k y^k X^(D + 1 - 2^k) U_k(q_k)
+        let mut batched = vec![Self::Field::zero(); self.max_degree + 1];
+        let mut y_power = Self::Field::one();
+        for (k, q_hat) in quotient_polys.iter().enumerate() {
+            let shift = self.max_degree + 1 - (1 << k);
+            for (i, c) in q_hat.get_coefs_ref().iter().enumerate() {
+                batched[shift + i] += y_power * c;
+            }
+            y_power *= y;
+        }
+        let batched = FpPolynomial::from_coefs(batched);
+        let batched_quotient_commitment = self.pcs.commit(&batched)?.0;
+        transcript.append_serializable(b"batched_quotient", &batched_quotient_commitment);
+        let x: Self::Field = transcript.challenge_scalar(b"x");
+        let z: Self::Field = transcript.challenge_scalar(b"z");
+
+        // zeta_x + z * Z_x, which vanishes at x.
+        let (identity_coefs, phi_n) = Self::identity_coefficients(&x, point);
+        let mut combined = f_hat.mul_scalar(&z);
+        combined.add_coef_assign(&(-z * value * phi_n), 0);
+        combined.add_assign(&batched);
+        let mut y_power = Self::Field::one();
+        for (k, (q_hat, c)) in quotient_polys.iter().zip(&identity_coefs).enumerate() {
+            let shift = (self.max_degree + 1 - (1 << k)) as u64;
+            let scalar = y_power * x.pow([shift]) + z * c;
+            combined.sub_assign(&q_hat.mul_scalar(&scalar));
+            y_power *= y;
+        }
+        let opening_proof = self.pcs.prove(&combined, &x, self.max_degree)?.0;
+
+        Ok(ZeromorphProof {
+            quotient_commitments,
+            batched_quotient_commitment,
+            opening_proof,
+        })
+    }
+
+    fn verify(
+        &self,
+        cm: &Self::Commitment,
+        point: &[Self::Field],
+        value: &Self::Field,
+        proof: &Self::Proof,
+    ) -> KZGResult<()> {
+        let n = point.len();
+        if n > self.max_num_vars() || proof.quotient_commitments.len() != n {
+            return Err(KZGError::PCSProveEvalError);
+        }
+
+        let mut transcript = Self::transcript(cm, point, value);
+        for c in proof.quotient_commitments.iter() {
+            transcript.append_serializable(b"quotient", c);
+        }
+        let y: Self::Field = transcript.challenge_scalar(b"y");
+        transcript.append_serializable(b"batched_quotient", &proof.batched_quotient_commitment);
+        let x: Self::Field = transcript.challenge_scalar(b"x");
+        let z: Self::Field = transcript.challenge_scalar(b"z");
+
+        let (identity_coefs, phi_n) = Self::identity_coefficients(&x, point);
+        let g1_0 = self.pcs.public_parameter_group_1[0];
+        let mut combined =
+            proof.batched_quotient_commitment + cm.0.mul(&z) - g1_0.mul(&(z * value * phi_n));
+        let mut y_power = Self::Field::one();
+        for (k, (c_k, c)) in proof
+            .quotient_commitments
+            .iter()
+            .zip(&identity_coefs)
+            .enumerate()
+        {
+            let shift = (self.max_degree + 1 - (1 << k)) as u64;
+            combined -= c_k.mul(&(y_power * x.pow([shift]) + z * c));
+            y_power *= y;
+        }
+
+        self.pcs.verify(
+            &KZGCommitment(combined),
+            self.max_degree,
+            &x,
+            &Self::Field::zero(),
+            &KZGCommitment(proof.opening_proof),
+        )
+    }
+
+    fn shrink_to_verifier_only(&self) -> KZGResult<Self> {
+        Ok(Self {
+            pcs: self.pcs.shrink_to_verifier_only()?,
+            max_degree: self.max_degree,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests_zeromorph {
+    use super::ZeromorphScheme;
+    use crate::{
+        backend::kzg::KZGCommitmentScheme, primitives::multilinear::DenseMultilinearPolynomial,
+        MultilinearPolyComScheme,
+    };
+    use ark_bls12_381::Bls12_381;
+    use ark_bn254::Bn254;
+    use ark_ec::pairing::Pairing;
+    use ark_ff::{One, UniformRand};
+    use ark_std::test_rng;
+
+    fn check_zeromorph<P: Pairing>(max_degree: usize) {
+        let mut prng = test_rng();
+        let pcs = ZeromorphScheme::new(KZGCommitmentScheme::<P>::new(max_degree, &mut prng));
+        let verifier = pcs.shrink_to_verifier_only().unwrap();
+        let max_num_vars = pcs.max_num_vars();
+        assert_eq!(verifier.max_num_vars(), max_num_vars);
+
+        for num_vars in 0..=max_num_vars {
+            let poly = DenseMultilinearPolynomial::random(&mut prng, num_vars);
+            let point: Vec<P::ScalarField> = (0..num_vars)
+                .map(|_| P::ScalarField::rand(&mut prng))
+                .collect();
+            let com = pcs.commit(&poly).unwrap();
+            let eval = pcs.eval(&poly, &point);
+            let proof = pcs.prove(&poly, &point).unwrap();
+            assert!(verifier.verify(&com, &point, &eval, &proof).is_ok());
+
+            let wrong_eval = eval + P::ScalarField::one();
+            assert!(verifier.verify(&com, &point, &wrong_eval, &proof).is_err());
+            if num_vars > 0 {
+                let mut wrong_proof = proof.clone();
+                wrong_proof.quotient_commitments[0] += proof.opening_proof;
+                assert!(verifier.verify(&com, &point, &eval, &wrong_proof).is_err());
+            }
+        }
+
+        let poly = DenseMultilinearPolynomial::random(&mut prng, max_num_vars + 1);
+        assert!(pcs.commit(&poly).is_err());
+    }
+
+    #[test]
+    fn test_zeromorph() {
+        check_zeromorph::<Bn254>(15);
+        check_zeromorph::<Bn254>(20);
+        check_zeromorph::<Bls12_381>(7);
+    }
+}
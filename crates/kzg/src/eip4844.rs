@@ -0,0 +1,293 @@
+//! Encodings of the EIP-4844 point evaluation precompile.
+
+use ark_bls12_381::{Fr, G1Affine, G1Projective};
+use ark_ec::CurveGroup;
+use ark_ff::{BigInteger, PrimeField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    backend::kzg::{KZGCommitment, KZGCommitmentSchemeBLS12_381, KZGOpenProof},
+    errs::{KZGError, KZGResult},
+    PolyComScheme,
+};
+
+/// Version byte of versioned hashes of KZG commitments.
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+/// Number of field elements in a blob.
+pub const FIELD_ELEMENTS_PER_BLOB: usize = 4096;
+
+/// Size of a compressed G1 point.
+pub const BYTES_PER_G1: usize = 48;
+
+/// Size of a field element.
+pub const BYTES_PER_FIELD_ELEMENT: usize = 32;
+
+/// Size of the input of the point evaluation precompile.
+pub const POINT_EVALUATION_INPUT_LENGTH: usize = 192;
+
+/// Size of the output of the point evaluation precompile.
+pub const POINT_EVALUATION_OUTPUT_LENGTH: usize = 64;
+
+/// Serialize a G1 point in the compressed ZCash format used by Ethereum.
+fn g1_to_bytes(point: &G1Projective) -> [u8; BYTES_PER_G1] {
+    let mut bytes = [0u8; BYTES_PER_G1];
+    point
+        .into_affine()
+        .serialize_compressed(&mut bytes[..])
+        .unwrap();
+    bytes
+}
+
+/// Deserialize a compressed G1 point, checking it is in the prime order subgroup.
+fn g1_from_bytes(bytes: &[u8]) -> KZGResult<G1Projective> {
+    if bytes.len() != BYTES_PER_G1 {
+        return Err(KZGError::DeserializationError);
+    }
+    G1Affine::deserialize_compressed(bytes)
+        .map(Into::into)
+        .map_err(|_| KZGError::DeserializationError)
+}
+
+/// Serialize a field element as 32 big-endian bytes.
+pub fn field_element_to_bytes(elem: &Fr) -> [u8; BYTES_PER_FIELD_ELEMENT] {
+    let mut bytes = [0u8; BYTES_PER_FIELD_ELEMENT];
+    bytes.copy_from_slice(&elem.into_bigint().to_bytes_be());
+    bytes
+}
+
+/// Deserialize a field element from 32 big-endian bytes, rejecting
+/// non-canonical encodings (values not below the modulus).
+pub fn field_element_from_bytes(bytes: &[u8]) -> KZGResult<Fr> {
+    if bytes.len() != BYTES_PER_FIELD_ELEMENT {
+        return Err(KZGError::DeserializationError);
+    }
+    let elem = Fr::from_be_bytes_mod_order(bytes);
+    if field_element_to_bytes(&elem) != bytes {
+        return Err(KZGError::DeserializationError);
+    }
+    Ok(elem)
+}
+
+impl KZGCommitment<G1Projective> {
+    /// Serialize the commitment in the 48-byte compressed format.
+    pub fn to_compressed_bytes(&self) -> [u8; BYTES_PER_G1] {
+        g1_to_bytes(&self.0)
+    }
+
+    /// Deserialize a commitment from the 48-byte compressed format.
+    pub fn from_compressed_bytes(bytes: &[u8]) -> KZGResult<Self> {
+        g1_from_bytes(bytes).map(KZGCommitment)
+    }
+
+    /// Return the versioned hash of the commitment:
+    /// `VERSIONED_HASH_VERSION_KZG || SHA256(commitment)[1..]`.
+    pub fn versioned_hash(&self) -> [u8; 32] {
+        let mut hash: [u8; 32] = Sha256::digest(self.to_compressed_bytes()).into();
+        hash[0] = VERSIONED_HASH_VERSION_KZG;
+        hash
+    }
+}
+
+impl KZGOpenProof<G1Projective> {
+    /// Serialize the proof in the 48-byte compressed format.
+    pub fn to_compressed_bytes(&self) -> [u8; BYTES_PER_G1] {
+        g1_to_bytes(&self.0)
+    }
+
+    /// Deserialize a proof from the 48-byte compressed format.
+    pub fn from_compressed_bytes(bytes: &[u8]) -> KZGResult<Self> {
+        g1_from_bytes(bytes).map(KZGOpenProof)
+    }
+}
+
+/// The input of the point evaluation precompile: a claim that the blob
+/// committed in `commitment`, with versioned hash `versioned_hash`,
+/// evaluates to `y` at `z`, together with the opening proof.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PointEvaluationInput {
+    /// The versioned hash of the commitment.
+    pub versioned_hash: [u8; 32],
+    /// The evaluation point.
+    pub z: Fr,
+    /// The claimed evaluation.
+    pub y: Fr,
+    /// The commitment to the blob polynomial.
+    pub commitment: KZGCommitment<G1Projective>,
+    /// The opening proof.
+    pub proof: KZGOpenProof<G1Projective>,
+}
+
+impl PointEvaluationInput {
+    /// Build the input for a claim, computing the versioned hash of the commitment.
+    pub fn new(
+        commitment: KZGCommitment<G1Projective>,
+        z: Fr,
+        y: Fr,
+        proof: KZGOpenProof<G1Projective>,
+    ) -> Self {
+        Self {
+            versioned_hash: commitment.versioned_hash(),
+            z,
+            y,
+            commitment,
+            proof,
+        }
+    }
+
+    /// Encode the input as `versioned_hash || z || y || commitment || proof`.
+    pub fn to_bytes(&self) -> [u8; POINT_EVALUATION_INPUT_LENGTH] {
+        let mut bytes = [0u8; POINT_EVALUATION_INPUT_LENGTH];
+        bytes[0..32].copy_from_slice(&self.versioned_hash);
+        bytes[32..64].copy_from_slice(&field_element_to_bytes(&self.z));
+        bytes[64..96].copy_from_slice(&field_element_to_bytes(&self.y));
+        bytes[96..144].copy_from_slice(&self.commitment.to_compressed_bytes());
+        bytes[144..192].copy_from_slice(&self.proof.to_compressed_bytes());
+        bytes
+    }
+
+    /// Decode an input, checking its length, that `z` and `y` are canonical
+    /// field elements and that the points are valid compressed G1 points.
+    /// The versioned hash is not checked against the commitment.
+    pub fn from_bytes(bytes: &[u8]) -> KZGResult<Self> {
+        if bytes.len() != POINT_EVALUATION_INPUT_LENGTH {
+            return Err(KZGError::DeserializationError);
+        }
+        let mut versioned_hash = [0u8; 32];
+        versioned_hash.copy_from_slice(&bytes[0..32]);
+        Ok(Self {
+            versioned_hash,
+            z: field_element_from_bytes(&bytes[32..64])?,
+            y: field_element_from_bytes(&bytes[64..96])?,
+            commitment: KZGCommitment::from_compressed_bytes(&bytes[96..144])?,
+            proof: KZGOpenProof::from_compressed_bytes(&bytes[144..192])?,
+        })
+    }
+}
+
+/// Return the output of a successful point evaluation precompile call:
+/// `FIELD_ELEMENTS_PER_BLOB` and `BLS_MODULUS` as 32-byte big-endian integers.
+pub fn point_evaluation_output() -> [u8; POINT_EVALUATION_OUTPUT_LENGTH] {
+    let mut output = [0u8; POINT_EVALUATION_OUTPUT_LENGTH];
+    output[24..32].copy_from_slice(&(FIELD_ELEMENTS_PER_BLOB as u64).to_be_bytes());
+    output[32..].copy_from_slice(&Fr::MODULUS.to_bytes_be());
+    output
+}
+
+/// Mirror the point evaluation precompile: decode `input`, check the
+/// versioned hash matches the commitment and verify the opening proof with
+/// the parameters of `pcs`, returning the precompile output on success.
+pub fn verify_point_evaluation(
+    pcs: &KZGCommitmentSchemeBLS12_381,
+    input: &[u8],
+) -> KZGResult<[u8; POINT_EVALUATION_OUTPUT_LENGTH]> {
+    let input = PointEvaluationInput::from_bytes(input)?;
+    if input.commitment.versioned_hash() != input.versioned_hash {
+        return Err(KZGError::VersionedHashError);
+    }
+    pcs.verify(
+        &input.commitment,
+        FIELD_ELEMENTS_PER_BLOB - 1,
+        &input.z,
+        &input.y,
+        &KZGCommitment(input.proof.0),
+    )?;
+    Ok(point_evaluation_output())
+}
+
+#[cfg(test)]
+mod tests_eip4844 {
+    use super::*;
+    use crate::primitives::poly::FpPolynomial;
+    use ark_ec::PrimeGroup;
+    use ark_ff::UniformRand;
+    use ark_std::{test_rng, Zero};
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_encodings() {
+        let generator = KZGCommitment(G1Projective::generator());
+        let bytes = generator.to_compressed_bytes();
+        assert_eq!(
+            bytes.to_vec(),
+            from_hex("97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb")
+        );
+        assert_eq!(
+            KZGCommitment::from_compressed_bytes(&bytes).unwrap(),
+            generator
+        );
+
+        // The commitment to the zero blob and its versioned hash.
+        let identity = KZGCommitment(G1Projective::zero());
+        let mut expected = vec![0u8; BYTES_PER_G1];
+        expected[0] = 0xc0;
+        assert_eq!(identity.to_compressed_bytes().to_vec(), expected);
+        assert_eq!(
+            identity.versioned_hash().to_vec(),
+            from_hex("010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014")
+        );
+
+        // Field elements are big-endian and must be canonical.
+        let one = field_element_to_bytes(&Fr::from(1u64));
+        assert_eq!(one[31], 1);
+        assert!(one[..31].iter().all(|b| *b == 0));
+        let modulus = Fr::MODULUS.to_bytes_be();
+        assert_eq!(
+            modulus,
+            from_hex("73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001")
+        );
+        assert!(field_element_from_bytes(&modulus).is_err());
+    }
+
+    #[test]
+    fn test_point_evaluation() {
+        let mut prng = test_rng();
+        let degree = 16;
+        let pcs = KZGCommitmentSchemeBLS12_381::new(degree, &mut prng);
+        let poly = FpPolynomial::random(&mut prng, degree);
+        let commitment = pcs.commit(&poly).unwrap();
+        let z = Fr::rand(&mut prng);
+        let y = poly.eval(&z);
+        let proof = KZGOpenProof(pcs.prove(&poly, &z, degree).unwrap().0);
+
+        let input = PointEvaluationInput::new(commitment.clone(), z, y, proof.clone());
+        let bytes = input.to_bytes();
+        assert_eq!(PointEvaluationInput::from_bytes(&bytes).unwrap(), input);
+
+        let verifier = pcs.shrink_to_verifier_only().unwrap();
+        let output = verify_point_evaluation(&verifier, &bytes).unwrap();
+        assert_eq!(output[..30], [0u8; 30]);
+        assert_eq!(output[30..32], [0x10, 0x00]);
+        assert_eq!(output[32..], Fr::MODULUS.to_bytes_be());
+
+        // Wrong evaluation.
+        let wrong = PointEvaluationInput::new(commitment, z, y + Fr::from(1u64), proof);
+        assert!(verify_point_evaluation(&verifier, &wrong.to_bytes()).is_err());
+
+        // Wrong versioned hash.
+        let mut wrong = input.clone();
+        wrong.versioned_hash[31] ^= 1;
+        assert!(matches!(
+            verify_point_evaluation(&verifier, &wrong.to_bytes()),
+            Err(KZGError::VersionedHashError)
+        ));
+
+        // Non-canonical evaluation point and malformed lengths.
+        let mut wrong = bytes;
+        wrong[32..64].copy_from_slice(&Fr::MODULUS.to_bytes_be());
+        assert!(verify_point_evaluation(&verifier, &wrong).is_err());
+        assert!(verify_point_evaluation(&verifier, &bytes[..191]).is_err());
+
+        // Invalid commitment encoding.
+        let mut wrong = bytes;
+        wrong[96] &= 0x7f;
+        assert!(verify_point_evaluation(&verifier, &wrong).is_err());
+    }
+}
@@ -0,0 +1,735 @@
+//! EIP-7594 (PeerDAS) cells and cell proofs.
+//!
+//! A blob holds the evaluations of a polynomial of degree less than
+//! `FIELD_ELEMENTS_PER_BLOB` at the roots of unity in bit-reversed order. It
+//! is Reed-Solomon extended to twice as many roots of unity, and the
+//! bit-reversed extended evaluations are split into cells of
+//! `FIELD_ELEMENTS_PER_CELL` elements, each of which is the evaluation on a
+//! coset of the subgroup of size `FIELD_ELEMENTS_PER_CELL` and is opened with
+//! one KZG proof. The proofs of all the cells are computed with FK20, and any
+//! half of the cells recovers the blob.
+//!
+//! The roots of unity are those of arkworks, generated by 7 as in the
+//! consensus specs. The sizes are configurable so that tests can run on small
+//! blobs; `PeerDAS::mainnet` uses the Ethereum parameters, with the trusted
+//! setup loaded by `KZGCommitmentScheme::from_trusted_setup`.
+
+use ark_bls12_381::{Bls12_381, Fr, G1Projective, G2Affine};
+use ark_ec::{pairing::Pairing, CurveGroup, PrimeGroup, VariableBaseMSM};
+use ark_ff::{batch_inversion, FftField, Field};
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use ark_serialize::CanonicalDeserialize;
+use ark_std::Zero;
+
+use crate::{
+    backend::kzg::{KZGCommitment, KZGCommitmentSchemeBLS12_381, KZGOpenProof},
+    eip4844::{
+        field_element_from_bytes, field_element_to_bytes, BYTES_PER_FIELD_ELEMENT, BYTES_PER_G1,
+    },
+    errs::{KZGError, KZGResult},
+    fk20::FK20,
+    primitives::{poly::FpPolynomial, transcript::Transcript},
+    PolyComScheme,
+};
+
+/// Number of field elements in an extended blob.
+pub const FIELD_ELEMENTS_PER_EXT_BLOB: usize = 8192;
+
+/// Number of field elements in a cell.
+pub const FIELD_ELEMENTS_PER_CELL: usize = 64;
+
+/// Number of cells in an extended blob.
+pub const CELLS_PER_EXT_BLOB: usize = 128;
+
+/// Size of a compressed G2 point.
+pub const BYTES_PER_G2: usize = 96;
+
+/// Domain separator of the batch verification challenge.
+const RANDOM_CHALLENGE_KZG_CELL_BATCH_DOMAIN: &[u8] = b"RCKZGCBATCH__V1_";
+
+/// A cell, the evaluations of the blob polynomial on a coset.
+pub type Cell = Vec<Fr>;
+
+/// Reverse the `log2(size)` low bits of `index`.
+pub fn reverse_bits(index: usize, size: usize) -> usize {
+    debug_assert!(size.is_power_of_two() && index < size);
+    index
+        .reverse_bits()
+        .checked_shr(usize::BITS - size.trailing_zeros())
+        .unwrap_or(0)
+}
+
+/// Permute `values`, whose length is a power of two, into bit-reversed order.
+pub fn bit_reversal_permutation<T: Clone>(values: &[T]) -> Vec<T> {
+    (0..values.len())
+        .map(|i| values[reverse_bits(i, values.len())].clone())
+        .collect()
+}
+
+/// Encode a cell as the concatenation of its big-endian field elements.
+pub fn cell_to_bytes(cell: &[Fr]) -> Vec<u8> {
+    cell.iter().flat_map(field_element_to_bytes).collect()
+}
+
+/// Decode a cell, rejecting non-canonical field elements.
+pub fn cell_from_bytes(bytes: &[u8]) -> KZGResult<Cell> {
+    let chunks = bytes.chunks_exact(BYTES_PER_FIELD_ELEMENT);
+    if !chunks.remainder().is_empty() {
+        return Err(KZGError::DeserializationError);
+    }
+    chunks.map(field_element_from_bytes).collect()
+}
+
+/// Decode hexadecimal bytes, with or without the `0x` prefix, of the
+/// expected length.
+fn bytes_from_hex(hex: &str, len: usize) -> KZGResult<Vec<u8>> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if hex.len() != 2 * len || !hex.is_ascii() {
+        return Err(KZGError::DeserializationError);
+    }
+    (0..len)
+        .map(|i| {
+            u8::from_str_radix(&hex[2 * i..2 * i + 2], 16)
+                .map_err(|_| KZGError::DeserializationError)
+        })
+        .collect()
+}
+
+impl KZGCommitmentSchemeBLS12_381 {
+    /// Load the Ethereum trusted setup in the `trusted_setup.txt` format of
+    /// c-kzg-4844: the numbers of G1 and G2 points, the G1 points in the
+    /// Lagrange basis of the roots of unity in bit-reversed order, the G2
+    /// points and, in the files since EIP-7594, the G1 points in the monomial
+    /// basis, all compressed and in hexadecimal. Without the monomial basis,
+    /// it is computed from the Lagrange basis with an FFT.
+    pub fn from_trusted_setup(text: &str) -> KZGResult<Self> {
+        let mut tokens = text.split_whitespace();
+        let mut counts = [0usize; 2];
+        for count in counts.iter_mut() {
+            *count = tokens
+                .next()
+                .and_then(|token| token.parse().ok())
+                .ok_or(KZGError::DeserializationError)?;
+        }
+        let [num_g1, num_g2] = counts;
+        if !num_g1.is_power_of_two() || num_g2 < 2 {
+            return Err(KZGError::DomainError);
+        }
+
+        let read_g1 = |tokens: &mut ark_std::str::SplitWhitespace<'_>| {
+            let bytes = bytes_from_hex(
+                tokens.next().ok_or(KZGError::DeserializationError)?,
+                BYTES_PER_G1,
+            )?;
+            KZGCommitment::from_compressed_bytes(&bytes).map(|point| point.0)
+        };
+        let lagrange = (0..num_g1)
+            .map(|_| read_g1(&mut tokens))
+            .collect::<KZGResult<Vec<_>>>()?;
+        let public_parameter_group_2 = (0..num_g2)
+            .map(|_| {
+                let bytes = bytes_from_hex(
+                    tokens.next().ok_or(KZGError::DeserializationError)?,
+                    BYTES_PER_G2,
+                )?;
+                G2Affine::deserialize_compressed(bytes.as_slice())
+                    .map(Into::into)
+                    .map_err(|_| KZGError::DeserializationError)
+            })
+            .collect::<KZGResult<Vec<_>>>()?;
+        let public_parameter_group_1 = match tokens.clone().next() {
+            Some(_) => (0..num_g1)
+                .map(|_| read_g1(&mut tokens))
+                .collect::<KZGResult<Vec<_>>>()?,
+            None => {
+                // [tau^j] = sum_k w^(jk) [L_k(tau)]
+                let domain =
+                    Radix2EvaluationDomain::<Fr>::new(num_g1).ok_or(KZGError::DomainError)?;
+                domain.fft(&bit_reversal_permutation(&lagrange))
+            }
+        };
+        if tokens.next().is_some() {
+            return Err(KZGError::DeserializationError);
+        }
+
+        Ok(Self {
+            public_parameter_group_1,
+            public_parameter_group_2,
+            max_degree: num_g1 - 1,
+        })
+    }
+}
+
+/// The PeerDAS context: the KZG parameters, with the powers of the secret in
+/// G2 up to the cell size, the FK20 precomputation and the domains.
+#[derive(Clone, Debug)]
+pub struct PeerDAS {
+    pcs: KZGCommitmentSchemeBLS12_381,
+    fk20: FK20<Bls12_381>,
+    blob_domain: Radix2EvaluationDomain<Fr>,
+    ext_domain: Radix2EvaluationDomain<Fr>,
+    cell_domain: Radix2EvaluationDomain<Fr>,
+}
+
+impl PeerDAS {
+    /// Build the context for blobs of `field_elements_per_blob` elements and
+    /// cells of `field_elements_per_cell` elements, both powers of two with
+    /// cells no larger than blobs.
+    pub fn new(
+        pcs: KZGCommitmentSchemeBLS12_381,
+        field_elements_per_blob: usize,
+        field_elements_per_cell: usize,
+    ) -> KZGResult<Self> {
+        if !field_elements_per_blob.is_power_of_two()
+            || !field_elements_per_cell.is_power_of_two()
+            || field_elements_per_cell > field_elements_per_blob
+        {
+            return Err(KZGError::DomainError);
+        }
+        if pcs.public_parameter_group_2.len() <= field_elements_per_cell {
+            return Err(KZGError::DegreeError);
+        }
+        let num_cells = 2 * field_elements_per_blob / field_elements_per_cell;
+        let fk20 = FK20::new(
+            &pcs,
+            field_elements_per_blob,
+            field_elements_per_cell,
+            num_cells,
+        )?;
+        let blob_domain = FpPolynomial::<Fr>::evaluation_domain(field_elements_per_blob)
+            .ok_or(KZGError::DomainError)?;
+        let ext_domain = FpPolynomial::<Fr>::evaluation_domain(2 * field_elements_per_blob)
+            .ok_or(KZGError::DomainError)?;
+        let cell_domain = FpPolynomial::<Fr>::evaluation_domain(field_elements_per_cell)
+            .ok_or(KZGError::DomainError)?;
+        Ok(Self {
+            pcs,
+            fk20,
+            blob_domain,
+            ext_domain,
+            cell_domain,
+        })
+    }
+
+    /// Build the context with the Ethereum sizes.
+    pub fn mainnet(pcs: KZGCommitmentSchemeBLS12_381) -> KZGResult<Self> {
+        Self::new(
+            pcs,
+            crate::eip4844::FIELD_ELEMENTS_PER_BLOB,
+            FIELD_ELEMENTS_PER_CELL,
+        )
+    }
+
+    /// Return the number of field elements in a blob.
+    pub fn field_elements_per_blob(&self) -> usize {
+        self.blob_domain.size()
+    }
+
+    /// Return the number of field elements in a cell.
+    pub fn field_elements_per_cell(&self) -> usize {
+        self.cell_domain.size()
+    }
+
+    /// Return the number of cells in an extended blob.
+    pub fn cells_per_ext_blob(&self) -> usize {
+        self.fk20.num_cosets()
+    }
+
+    /// Return the shift of the coset of cell `cell_index`.
+    pub fn coset_shift(&self, cell_index: usize) -> Fr {
+        self.fk20
+            .coset_shift(reverse_bits(cell_index, self.cells_per_ext_blob()))
+    }
+
+    /// Return the polynomial whose bit-reversed evaluations are `blob`.
+    pub fn blob_to_polynomial(&self, blob: &[Fr]) -> KZGResult<FpPolynomial<Fr>> {
+        if blob.len() != self.field_elements_per_blob() {
+            return Err(KZGError::DegreeError);
+        }
+        Ok(FpPolynomial::ifft_with_domain(
+            &self.blob_domain,
+            &bit_reversal_permutation(blob),
+        ))
+    }
+
+    /// Commit to a blob.
+    pub fn blob_to_kzg_commitment(&self, blob: &[Fr]) -> KZGResult<KZGCommitment<G1Projective>> {
+        self.pcs.commit(&self.blob_to_polynomial(blob)?)
+    }
+
+    /// Extend a blob and split it into cells.
+    pub fn compute_cells(&self, blob: &[Fr]) -> KZGResult<Vec<Cell>> {
+        Ok(self.polynomial_to_cells(&self.blob_to_polynomial(blob)?))
+    }
+
+    /// Extend a blob, split it into cells and compute the proofs of all the
+    /// cells with FK20.
+    pub fn compute_cells_and_kzg_proofs(
+        &self,
+        blob: &[Fr],
+    ) -> KZGResult<(Vec<Cell>, Vec<KZGOpenProof<G1Projective>>)> {
+        let poly = self.blob_to_polynomial(blob)?;
+        Ok((
+            self.polynomial_to_cells(&poly),
+            self.polynomial_to_proofs(&poly)?,
+        ))
+    }
+
+    /// Verify a batch of cell proofs, where the `i`-th cell has index
+    /// `cell_indices[i]` in the blob committed in `commitments[i]`.
+    ///
+    /// With a random `r`, and `I_k` the interpolation polynomial of cell `k`
+    /// on the coset of shift `h_k`, this checks the universal equation
+    /// `e(sum r^k proof_k, [tau^l]) =
+    /// e(sum r^k (C_k - [I_k(tau)] + h_k^l proof_k), [1])`.
+    pub fn verify_cell_kzg_proof_batch(
+        &self,
+        commitments: &[KZGCommitment<G1Projective>],
+        cell_indices: &[usize],
+        cells: &[Cell],
+        proofs: &[KZGOpenProof<G1Projective>],
+    ) -> KZGResult<()> {
+        let n = cells.len();
+        if commitments.len() != n || cell_indices.len() != n || proofs.len() != n {
+            return Err(KZGError::CellError);
+        }
+        self.check_cells(cell_indices, cells)?;
+        if n == 0 {
+            return Ok(());
+        }
+
+        let mut transcript = Transcript::new(RANDOM_CHALLENGE_KZG_CELL_BATCH_DOMAIN);
+        transcript.append_message(
+            b"field elements per blob",
+            &(self.field_elements_per_blob() as u64).to_le_bytes(),
+        );
+        transcript.append_message(
+            b"field elements per cell",
+            &(self.field_elements_per_cell() as u64).to_le_bytes(),
+        );
+        for (((commitment, index), cell), proof) in
+            commitments.iter().zip(cell_indices).zip(cells).zip(proofs)
+        {
+            transcript.append_serializable(b"commitment", &commitment.0);
+            transcript.append_message(b"cell index", &(*index as u64).to_le_bytes());
+            for elem in cell {
+                transcript.append_serializable(b"cell", elem);
+            }
+            transcript.append_serializable(b"proof", &proof.0);
+        }
+        let r: Fr = transcript.challenge_scalar(b"r");
+
+        let mut powers = Vec::with_capacity(n);
+        let mut power = Fr::ONE;
+        for _ in 0..n {
+            powers.push(power);
+            power *= r;
+        }
+
+        let mut interpolation = FpPolynomial::zero();
+        let mut shifted_powers = Vec::with_capacity(n);
+        for ((index, cell), power) in cell_indices.iter().zip(cells).zip(&powers) {
+            let shift = self.coset_shift(*index);
+            interpolation.add_assign(&self.interpolate_cell(&shift, cell).mul_scalar(power));
+            shifted_powers.push(*power * shift.pow([self.field_elements_per_cell() as u64]));
+        }
+
+        let commitment_points: Vec<G1Projective> = commitments.iter().map(|c| c.0).collect();
+        let proof_points: Vec<G1Projective> = proofs.iter().map(|p| p.0).collect();
+        let commitment_points = G1Projective::normalize_batch(&commitment_points);
+        let proof_points = G1Projective::normalize_batch(&proof_points);
+        let sum_commitments = G1Projective::msm(&commitment_points, &powers).unwrap();
+        let sum_proofs = G1Projective::msm(&proof_points, &powers).unwrap();
+        let sum_shifted_proofs = G1Projective::msm(&proof_points, &shifted_powers).unwrap();
+        let interpolation_commitment = self.pcs.commit(&interpolation)?.0;
+
+        let lhs = Bls12_381::pairing(
+            sum_proofs,
+            self.pcs.public_parameter_group_2[self.field_elements_per_cell()],
+        );
+        let rhs = Bls12_381::pairing(
+            sum_commitments - interpolation_commitment + sum_shifted_proofs,
+            <Bls12_381 as Pairing>::G2::generator(),
+        );
+        if lhs == rhs {
+            Ok(())
+        } else {
+            Err(KZGError::PCSProveEvalError)
+        }
+    }
+
+    /// Recover all the cells and proofs of a blob from at least half of its
+    /// cells.
+    ///
+    /// With `Z` vanishing on the cosets of the missing cells and `E` the
+    /// extended evaluations with zeros for the missing cells, `E * Z` agrees
+    /// with `P * Z` on the whole extended domain, so the blob polynomial `P`
+    /// is `(E * Z) / Z`, divided on a coset where `Z` does not vanish.
+    pub fn recover_cells_and_kzg_proofs(
+        &self,
+        cell_indices: &[usize],
+        cells: &[Cell],
+    ) -> KZGResult<(Vec<Cell>, Vec<KZGOpenProof<G1Projective>>)> {
+        if cell_indices.len() != cells.len() {
+            return Err(KZGError::CellError);
+        }
+        self.check_cells(cell_indices, cells)?;
+        let num_cells = self.cells_per_ext_blob();
+        let cell_size = self.field_elements_per_cell();
+        let mut present = vec![false; num_cells];
+        for index in cell_indices {
+            if present[*index] {
+                return Err(KZGError::CellError);
+            }
+            present[*index] = true;
+        }
+        if 2 * cell_indices.len() < num_cells {
+            return Err(KZGError::RecoveryError);
+        }
+
+        let mut ext_evals = vec![Fr::zero(); self.ext_domain.size()];
+        for (index, cell) in cell_indices.iter().zip(cells) {
+            ext_evals[index * cell_size..(index + 1) * cell_size].copy_from_slice(cell);
+        }
+        let ext_evals = bit_reversal_permutation(&ext_evals);
+
+        // Z(X) = prod_{missing} (X^l - h^l)
+        let missing_roots: Vec<Fr> = (0..num_cells)
+            .filter(|i| !present[*i])
+            .map(|i| self.coset_shift(i).pow([cell_size as u64]))
+            .collect();
+        let short_vanishing = FpPolynomial::from_zeroes(&missing_roots);
+        let mut vanishing_coefs = vec![Fr::zero(); self.ext_domain.size()];
+        for (i, coef) in short_vanishing.get_coefs_ref().iter().enumerate() {
+            vanishing_coefs[i * cell_size] = *coef;
+        }
+        let vanishing = FpPolynomial::from_coefs(vanishing_coefs);
+
+        let product_evals: Vec<Fr> = vanishing
+            .fft_with_domain(&self.ext_domain)
+            .iter()
+            .zip(&ext_evals)
+            .map(|(z, e)| *z * e)
+            .collect();
+        let product = FpPolynomial::ifft_with_domain(&self.ext_domain, &product_evals);
+
+        let shift = Fr::GENERATOR;
+        let mut vanishing_coset = vanishing.coset_fft_with_domain(&self.ext_domain, &shift);
+        batch_inversion(&mut vanishing_coset);
+        let quotient_evals: Vec<Fr> = product
+            .coset_fft_with_domain(&self.ext_domain, &shift)
+            .iter()
+            .zip(&vanishing_coset)
+            .map(|(p, z)| *p * z)
+            .collect();
+        let poly = FpPolynomial::coset_ifft_with_domain(
+            &self.ext_domain,
+            &quotient_evals,
+            &shift.inverse().unwrap(),
+        );
+        if poly.get_coefs_ref().len() > self.field_elements_per_blob() {
+            return Err(KZGError::RecoveryError);
+        }
+
+        Ok((
+            self.polynomial_to_cells(&poly),
+            self.polynomial_to_proofs(&poly)?,
+        ))
+    }
+
+    fn check_cells(&self, cell_indices: &[usize], cells: &[Cell]) -> KZGResult<()> {
+        if cell_indices
+            .iter()
+            .any(|index| *index >= self.cells_per_ext_blob())
+            || cells
+                .iter()
+                .any(|cell| cell.len() != self.field_elements_per_cell())
+        {
+            return Err(KZGError::CellError);
+        }
+        Ok(())
+    }
+
+    fn polynomial_to_cells(&self, poly: &FpPolynomial<Fr>) -> Vec<Cell> {
+        let evals = bit_reversal_permutation(&poly.fft_with_domain(&self.ext_domain));
+        evals
+            .chunks(self.field_elements_per_cell())
+            .map(|cell| cell.to_vec())
+            .collect()
+    }
+
+    fn polynomial_to_proofs(
+        &self,
+        poly: &FpPolynomial<Fr>,
+    ) -> KZGResult<Vec<KZGOpenProof<G1Projective>>> {
+        let proofs = self.fk20.compute_proofs(poly)?;
+        Ok(bit_reversal_permutation(&proofs)
+            .into_iter()
+            .map(KZGOpenProof)
+            .collect())
+    }
+
+    /// Interpolate the bit-reversed evaluations `cell` on the coset of shift `shift`.
+    fn interpolate_cell(&self, shift: &Fr, cell: &[Fr]) -> FpPolynomial<Fr> {
+        FpPolynomial::coset_ifft_with_domain(
+            &self.cell_domain,
+            &bit_reversal_permutation(cell),
+            &shift.inverse().unwrap(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests_eip7594 {
+    use super::*;
+    use crate::backend::kzg::KZGCommitmentScheme;
+    use ark_ff::UniformRand;
+    use ark_std::test_rng;
+
+    const BLOB_SIZE: usize = 32;
+    const CELL_SIZE: usize = 4;
+
+    fn setup() -> PeerDAS {
+        let mut prng = test_rng();
+        let pcs = KZGCommitmentScheme::new_with_g2_degree(BLOB_SIZE - 1, CELL_SIZE, &mut prng);
+        PeerDAS::new(pcs, BLOB_SIZE, CELL_SIZE).unwrap()
+    }
+
+    fn random_blob() -> Vec<Fr> {
+        let mut prng = test_rng();
+        (0..BLOB_SIZE).map(|_| Fr::rand(&mut prng)).collect()
+    }
+
+    #[test]
+    fn test_new() {
+        let mut prng = test_rng();
+        let pcs = KZGCommitmentScheme::new_with_g2_degree(BLOB_SIZE - 1, CELL_SIZE, &mut prng);
+        for (blob_size, cell_size) in [
+            (BLOB_SIZE, 0),
+            (BLOB_SIZE, 3),
+            (24, CELL_SIZE),
+            (2, CELL_SIZE),
+        ] {
+            assert!(matches!(
+                PeerDAS::new(pcs.clone(), blob_size, cell_size),
+                Err(KZGError::DomainError)
+            ));
+        }
+        // The cell size needs as many powers of the secret in G2.
+        assert!(matches!(
+            PeerDAS::new(pcs, BLOB_SIZE, 2 * CELL_SIZE),
+            Err(KZGError::DegreeError)
+        ));
+    }
+
+    #[test]
+    fn test_trusted_setup() {
+        use ark_serialize::CanonicalSerialize;
+
+        let mut prng = test_rng();
+        let pcs: KZGCommitmentSchemeBLS12_381 =
+            KZGCommitmentScheme::new_with_g2_degree(BLOB_SIZE - 1, CELL_SIZE, &mut prng);
+        let hex = |bytes: &[u8]| -> String { bytes.iter().map(|b| format!("{:02x}", b)).collect() };
+        let g1_hex = |points: &[G1Projective]| -> Vec<String> {
+            points
+                .iter()
+                .map(|point| hex(&KZGCommitment(*point).to_compressed_bytes()))
+                .collect()
+        };
+        let domain = Radix2EvaluationDomain::<Fr>::new(BLOB_SIZE).unwrap();
+        let lagrange = bit_reversal_permutation(&domain.ifft(&pcs.public_parameter_group_1));
+        let g2: Vec<String> = pcs
+            .public_parameter_group_2
+            .iter()
+            .map(|point| {
+                let mut bytes = vec![];
+                point
+                    .into_affine()
+                    .serialize_compressed(&mut bytes)
+                    .unwrap();
+                format!("0x{}", hex(&bytes))
+            })
+            .collect();
+
+        let mut lines = vec![BLOB_SIZE.to_string(), (CELL_SIZE + 1).to_string()];
+        lines.extend(g1_hex(&lagrange));
+        lines.extend(g2);
+        // The files before EIP-7594 end with the G2 points.
+        let text = lines.join("\n");
+        assert_eq!(KZGCommitmentScheme::from_trusted_setup(&text).unwrap(), pcs);
+        lines.extend(g1_hex(&pcs.public_parameter_group_1));
+        let text = lines.join("\n") + "\n";
+        let loaded = KZGCommitmentScheme::from_trusted_setup(&text).unwrap();
+        assert_eq!(loaded, pcs);
+        assert!(PeerDAS::new(loaded, BLOB_SIZE, CELL_SIZE).is_ok());
+
+        // Truncated, trailing or corrupted points.
+        let text = lines[..lines.len() - 1].join("\n");
+        assert!(KZGCommitmentScheme::from_trusted_setup(&text).is_err());
+        let text = lines.join("\n") + "\n00";
+        assert!(KZGCommitmentScheme::from_trusted_setup(&text).is_err());
+        let mut corrupted = lines.clone();
+        corrupted[2].replace_range(2..4, "00");
+        assert!(KZGCommitmentScheme::from_trusted_setup(&corrupted.join("\n")).is_err());
+        assert!(matches!(
+            KZGCommitmentScheme::from_trusted_setup("24\n5"),
+            Err(KZGError::DomainError)
+        ));
+    }
+
+    #[test]
+    fn test_bit_reversal() {
+        assert_eq!(reverse_bits(0, 1), 0);
+        assert_eq!(reverse_bits(1, 8), 4);
+        assert_eq!(reverse_bits(6, 8), 3);
+        let values: Vec<usize> = (0..16).collect();
+        let permuted = bit_reversal_permutation(&values);
+        assert_eq!(permuted[1], 8);
+        assert_eq!(bit_reversal_permutation(&permuted), values);
+    }
+
+    #[test]
+    fn test_cell_bytes() {
+        let cell = random_blob();
+        let bytes = cell_to_bytes(&cell);
+        assert_eq!(bytes.len(), BLOB_SIZE * BYTES_PER_FIELD_ELEMENT);
+        assert_eq!(cell_from_bytes(&bytes).unwrap(), cell);
+        assert!(cell_from_bytes(&bytes[1..]).is_err());
+    }
+
+    #[test]
+    fn test_compute_cells_and_proofs() {
+        let das = setup();
+        let blob = random_blob();
+        let poly = das.blob_to_polynomial(&blob).unwrap();
+        let (cells, proofs) = das.compute_cells_and_kzg_proofs(&blob).unwrap();
+        assert_eq!(cells.len(), das.cells_per_ext_blob());
+        assert_eq!(cells, das.compute_cells(&blob).unwrap());
+
+        // The first half of the extended blob is the blob itself.
+        assert_eq!(cells[..cells.len() / 2].concat(), blob);
+
+        for (index, (cell, proof)) in cells.iter().zip(&proofs).enumerate() {
+            let shift = das.coset_shift(index);
+            let interpolation = das.interpolate_cell(&shift, cell);
+            let roots = bit_reversal_permutation(&das.cell_domain.elements().collect::<Vec<_>>());
+            for (root, eval) in roots.iter().zip(cell) {
+                assert_eq!(poly.eval(&(shift * root)), *eval);
+                assert_eq!(interpolation.eval(&(shift * root)), *eval);
+            }
+
+            let mut divisor = vec![Fr::zero(); CELL_SIZE + 1];
+            divisor[0] = -shift.pow([CELL_SIZE as u64]);
+            divisor[CELL_SIZE] = Fr::ONE;
+            let (quotient, _) = poly.div_rem(&FpPolynomial::from_coefs(divisor));
+            assert_eq!(das.pcs.commit(&quotient).unwrap().0, proof.0);
+        }
+    }
+
+    #[test]
+    fn test_verify_cell_kzg_proof_batch() {
+        let das = setup();
+        let mut prng = test_rng();
+        let blobs: Vec<Vec<Fr>> = (0..2)
+            .map(|_| (0..BLOB_SIZE).map(|_| Fr::rand(&mut prng)).collect())
+            .collect();
+
+        let mut commitments = vec![];
+        let mut indices = vec![];
+        let mut cells = vec![];
+        let mut proofs = vec![];
+        for blob in &blobs {
+            let commitment = das.blob_to_kzg_commitment(blob).unwrap();
+            let (blob_cells, blob_proofs) = das.compute_cells_and_kzg_proofs(blob).unwrap();
+            for index in [0, 3, 5, 3, das.cells_per_ext_blob() - 1] {
+                commitments.push(commitment.clone());
+                indices.push(index);
+                cells.push(blob_cells[index].clone());
+                proofs.push(blob_proofs[index].clone());
+            }
+        }
+        das.verify_cell_kzg_proof_batch(&commitments, &indices, &cells, &proofs)
+            .unwrap();
+        das.verify_cell_kzg_proof_batch(&[], &[], &[], &[]).unwrap();
+
+        // Wrong cell, index, proof or commitment.
+        let mut bad_cells = cells.clone();
+        bad_cells[2][1] += Fr::ONE;
+        assert!(das
+            .verify_cell_kzg_proof_batch(&commitments, &indices, &bad_cells, &proofs)
+            .is_err());
+        let mut bad_indices = indices.clone();
+        bad_indices[0] = 1;
+        assert!(das
+            .verify_cell_kzg_proof_batch(&commitments, &bad_indices, &cells, &proofs)
+            .is_err());
+        let mut bad_proofs = proofs.clone();
+        bad_proofs.swap(0, 1);
+        assert!(das
+            .verify_cell_kzg_proof_batch(&commitments, &indices, &cells, &bad_proofs)
+            .is_err());
+        let mut bad_commitments = commitments.clone();
+        bad_commitments.swap(0, 9);
+        assert!(das
+            .verify_cell_kzg_proof_batch(&bad_commitments, &indices, &cells, &proofs)
+            .is_err());
+
+        // Malformed inputs.
+        let mut bad_indices = indices.clone();
+        bad_indices[0] = das.cells_per_ext_blob();
+        assert!(matches!(
+            das.verify_cell_kzg_proof_batch(&commitments, &bad_indices, &cells, &proofs),
+            Err(KZGError::CellError)
+        ));
+        assert!(das
+            .verify_cell_kzg_proof_batch(&commitments[1..], &indices, &cells, &proofs)
+            .is_err());
+    }
+
+    #[test]
+    fn test_recover_cells_and_kzg_proofs() {
+        let das = setup();
+        let blob = random_blob();
+        let (cells, proofs) = das.compute_cells_and_kzg_proofs(&blob).unwrap();
+        let num_cells = das.cells_per_ext_blob();
+
+        // Any half of the cells, all of them, or more than half.
+        for indices in [
+            (0..num_cells).filter(|i| i % 2 == 1).collect::<Vec<_>>(),
+            (num_cells / 2..num_cells).rev().collect(),
+            (0..num_cells).collect(),
+            (0..num_cells).filter(|i| i % 4 != 0).collect(),
+        ] {
+            let partial: Vec<Cell> = indices.iter().map(|i| cells[*i].clone()).collect();
+            let (recovered_cells, recovered_proofs) = das
+                .recover_cells_and_kzg_proofs(&indices, &partial)
+                .unwrap();
+            assert_eq!(recovered_cells, cells);
+            assert_eq!(recovered_proofs, proofs);
+        }
+
+        // Too few or duplicated cells.
+        let indices: Vec<usize> = (1..num_cells / 2).collect();
+        let partial: Vec<Cell> = indices.iter().map(|i| cells[*i].clone()).collect();
+        assert!(matches!(
+            das.recover_cells_and_kzg_proofs(&indices, &partial),
+            Err(KZGError::RecoveryError)
+        ));
+        let mut indices: Vec<usize> = (0..num_cells / 2).collect();
+        indices[1] = 0;
+        let partial: Vec<Cell> = indices.iter().map(|i| cells[*i].clone()).collect();
+        assert!(matches!(
+            das.recover_cells_and_kzg_proofs(&indices, &partial),
+            Err(KZGError::CellError)
+        ));
+
+        // Inconsistent cells beyond half do not recover a blob.
+        let indices: Vec<usize> = (0..num_cells / 2 + 1).collect();
+        let mut partial: Vec<Cell> = indices.iter().map(|i| cells[*i].clone()).collect();
+        partial[0][0] += Fr::ONE;
+        assert!(matches!(
+            das.recover_cells_and_kzg_proofs(&indices, &partial),
+            Err(KZGError::RecoveryError)
+        ));
+    }
+}
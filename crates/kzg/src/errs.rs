@@ -14,4 +14,18 @@ pub enum KZGError {
     DegreeError,
     #[error("PCS prove eval error")]
     PCSProveEvalError,
+    #[error("Versioned hash mismatch")]
+    VersionedHashError,
+    #[error("Invalid evaluation domain")]
+    DomainError,
+    #[error("Invalid cell")]
+    CellError,
+    #[error("Cell recovery error")]
+    RecoveryError,
+    #[error("Index out of range")]
+    IndexError,
+    #[error("Verkle proof error")]
+    VerkleProofError,
+    #[error("Opening points differ")]
+    PointMismatchError,
 }
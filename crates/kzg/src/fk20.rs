@@ -0,0 +1,196 @@
+//! The FK20 algorithm computing the KZG proofs of a polynomial on all the
+//! cosets of a domain at once (Feist and Khovratovich, "Fast amortized KZG
+//! proofs").
+//!
+//! Writing `f(X) = sum_t X^(t * l) * F_t(X)` with `deg F_t < l`, the quotient
+//! of `f` by `X^l - a` commits to `sum_u a^u * h_u`, where the `h_u` are sums
+//! of `l` Toeplitz matrix-vector products of the coefficients with the SRS.
+//! The products are computed with circulant embeddings and FFTs over G1, and
+//! the proofs of all the cosets `w^i * <w^m>` are the FFT of the `h_u`.
+
+use ark_ec::pairing::Pairing;
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use ark_std::Zero;
+
+use crate::{
+    backend::kzg::KZGCommitmentScheme,
+    errs::{KZGError, KZGResult},
+    primitives::poly::FpPolynomial,
+};
+
+/// Precomputed FK20 parameters for polynomials of `poly_len` coefficients
+/// opened on the `num_cosets` cosets of size `coset_size` of the domain of
+/// size `num_cosets * coset_size`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FK20<P: Pairing> {
+    poly_len: usize,
+    coset_size: usize,
+    /// The domain of size `num_cosets * coset_size`.
+    domain: Radix2EvaluationDomain<P::ScalarField>,
+    /// The domain of the circulant embeddings, of size `2 * poly_len / coset_size`.
+    toeplitz_domain: Radix2EvaluationDomain<P::ScalarField>,
+    /// The domain of the coset shifts raised to `coset_size`, of size `num_cosets`.
+    proof_domain: Radix2EvaluationDomain<P::ScalarField>,
+    /// For each offset `r < coset_size`, the FFT of `[tau^(d * coset_size + r)]_1`.
+    srs_fft: Vec<Vec<P::G1>>,
+}
+
+impl<P: Pairing> FK20<P> {
+    /// Precompute the FFTs of the SRS of `pcs`.
+    /// All the sizes must be powers of two, `coset_size` must divide
+    /// `poly_len` and there must be at least `poly_len / coset_size` cosets.
+    pub fn new(
+        pcs: &KZGCommitmentScheme<P>,
+        poly_len: usize,
+        coset_size: usize,
+        num_cosets: usize,
+    ) -> KZGResult<Self> {
+        if !poly_len.is_power_of_two()
+            || !coset_size.is_power_of_two()
+            || !num_cosets.is_power_of_two()
+            || coset_size > poly_len
+            || num_cosets < poly_len / coset_size
+        {
+            return Err(KZGError::DomainError);
+        }
+        if pcs.public_parameter_group_1.len() < poly_len {
+            return Err(KZGError::DegreeError);
+        }
+        let num_blocks = poly_len / coset_size;
+        let domain =
+            Radix2EvaluationDomain::new(num_cosets * coset_size).ok_or(KZGError::DomainError)?;
+        let toeplitz_domain =
+            Radix2EvaluationDomain::new(2 * num_blocks).ok_or(KZGError::DomainError)?;
+        let proof_domain = Radix2EvaluationDomain::new(num_cosets).ok_or(KZGError::DomainError)?;
+
+        let srs_fft = (0..coset_size)
+            .map(|r| {
+                let column: Vec<P::G1> = (0..num_blocks.saturating_sub(1))
+                    .map(|d| pcs.public_parameter_group_1[d * coset_size + r])
+                    .collect();
+                toeplitz_domain.fft(&column)
+            })
+            .collect();
+
+        Ok(Self {
+            poly_len,
+            coset_size,
+            domain,
+            toeplitz_domain,
+            proof_domain,
+            srs_fft,
+        })
+    }
+
+    /// Return the maximal number of coefficients of the polynomials.
+    pub fn poly_len(&self) -> usize {
+        self.poly_len
+    }
+
+    /// Return the size of the cosets.
+    pub fn coset_size(&self) -> usize {
+        self.coset_size
+    }
+
+    /// Return the number of cosets.
+    pub fn num_cosets(&self) -> usize {
+        self.proof_domain.size()
+    }
+
+    /// Return the shift `w^i` of the `i`-th coset, where `w` generates the
+    /// domain of size `num_cosets * coset_size`.
+    pub fn coset_shift(&self, i: usize) -> P::ScalarField {
+        self.domain.element(i)
+    }
+
+    /// Compute the proofs of `poly` on all the cosets, in the natural order:
+    /// the `i`-th proof commits to the quotient of `poly` by
+    /// `X^coset_size - coset_shift(i)^coset_size`.
+    pub fn compute_proofs(&self, poly: &FpPolynomial<P::ScalarField>) -> KZGResult<Vec<P::G1>> {
+        let coefs = poly.get_coefs_ref();
+        if coefs.len() > self.poly_len {
+            return Err(KZGError::DegreeError);
+        }
+        let num_blocks = self.poly_len / self.coset_size;
+        let coef = |i: usize| coefs.get(i).copied().unwrap_or_default();
+
+        // Sum the Toeplitz products in the Fourier domain.
+        let mut acc = vec![P::G1::zero(); self.toeplitz_domain.size()];
+        for (r, srs_fft) in self.srs_fft.iter().enumerate() {
+            let column: Vec<P::ScalarField> = (0..num_blocks)
+                .map(|j| coef((num_blocks - 1 - j) * self.coset_size + r))
+                .collect();
+            let column_fft = self.toeplitz_domain.fft(&column);
+            for ((a, s), c) in acc.iter_mut().zip(srs_fft).zip(column_fft) {
+                *a += *s * c;
+            }
+        }
+        let convolution = self.toeplitz_domain.ifft(&acc);
+
+        let h: Vec<P::G1> = (0..num_blocks)
+            .map(|u| {
+                if u + 2 <= num_blocks {
+                    convolution[num_blocks - 2 - u]
+                } else {
+                    P::G1::zero()
+                }
+            })
+            .collect();
+        Ok(self.proof_domain.fft(&h))
+    }
+}
+
+#[cfg(test)]
+mod tests_fk20 {
+    use super::FK20;
+    use crate::{backend::kzg::KZGCommitmentScheme, primitives::poly::FpPolynomial, PolyComScheme};
+    use ark_bls12_381::Bls12_381;
+    use ark_bn254::Bn254;
+    use ark_ec::pairing::Pairing;
+    use ark_ff::{Field, One, Zero};
+    use ark_std::test_rng;
+
+    fn check_fk20<P: Pairing>(poly_len: usize, coset_size: usize, num_cosets: usize) {
+        let mut prng = test_rng();
+        let pcs = KZGCommitmentScheme::<P>::new(poly_len - 1, &mut prng);
+        let fk20 = FK20::new(&pcs, poly_len, coset_size, num_cosets).unwrap();
+        assert_eq!(fk20.num_cosets(), num_cosets);
+
+        let poly = FpPolynomial::random(&mut prng, poly_len - 1);
+        let proofs = fk20.compute_proofs(&poly).unwrap();
+        assert_eq!(proofs.len(), num_cosets);
+        for (i, proof) in proofs.iter().enumerate() {
+            // X^l - h^l
+            let mut divisor = vec![P::ScalarField::zero(); coset_size + 1];
+            divisor[0] = -fk20.coset_shift(i).pow([coset_size as u64]);
+            divisor[coset_size] = P::ScalarField::one();
+            let (quotient, _) = poly.div_rem(&FpPolynomial::from_coefs(divisor));
+            assert_eq!(pcs.commit(&quotient).unwrap().0, *proof);
+        }
+
+        // Polynomials of lower degree.
+        let poly = FpPolynomial::random(&mut prng, coset_size / 2);
+        let proofs = fk20.compute_proofs(&poly).unwrap();
+        assert!(proofs.iter().all(|proof| proof.is_zero()));
+    }
+
+    #[test]
+    fn test_fk20() {
+        check_fk20::<Bn254>(16, 4, 8);
+        check_fk20::<Bn254>(16, 1, 16);
+        check_fk20::<Bls12_381>(32, 4, 16);
+        check_fk20::<Bls12_381>(8, 8, 2);
+    }
+
+    #[test]
+    fn test_fk20_parameters() {
+        let mut prng = test_rng();
+        let pcs = KZGCommitmentScheme::<Bn254>::new(15, &mut prng);
+        assert!(FK20::new(&pcs, 16, 3, 8).is_err());
+        assert!(FK20::new(&pcs, 16, 4, 2).is_err());
+        assert!(FK20::new(&pcs, 32, 4, 16).is_err());
+        let fk20 = FK20::new(&pcs, 16, 4, 4).unwrap();
+        let poly = FpPolynomial::random(&mut prng, 16);
+        assert!(fk20.compute_proofs(&poly).is_err());
+    }
+}
@@ -1,12 +1,19 @@
-pub(crate) mod backend;
+pub mod accumulation;
+pub mod aggregation;
+pub mod backend;
+pub mod eip4844;
+pub mod eip7594;
 pub mod errs;
+pub mod fk20;
 pub mod primitives;
+pub mod vector_commitment;
+pub mod verkle;
 
 use ark_ff::PrimeField;
 use ark_std::fmt::Debug;
 use errs::KZGResult;
 
-use crate::primitives::poly::FpPolynomial;
+use crate::primitives::{multilinear::DenseMultilinearPolynomial, poly::FpPolynomial};
 
 /// The trait for serialization to bytes
 pub trait ToBytes {
@@ -52,14 +59,20 @@ pub trait PolyComScheme: Sized + Eq + PartialEq + Clone {
     /// Type of prime field.
     type Field: PrimeField + Debug + Sync + Send;
 
-    /// Type of commitment produces, need to implement `HomomorphicPolyComElem`.
-    type Commitment: HomomorphicPolyComElem<Scalar = Self::Field>
+    /// Type of commitment produces.
+    type Commitment: ToBytes
         + Debug
         + Default
         + PartialEq
         + Eq
         + Clone
-        + Sync;
+        + Sync
+        + Send
+        + serde::Serialize
+        + serde::de::DeserializeOwned;
+
+    /// Type of evaluation proof.
+    type Proof: Debug + PartialEq + Eq + Clone + serde::Serialize + serde::de::DeserializeOwned;
 
     /// Return maximal supported degree
     fn max_degree(&self) -> usize;
@@ -76,7 +89,7 @@ pub trait PolyComScheme: Sized + Eq + PartialEq + Clone {
         polynomial: &FpPolynomial<Self::Field>,
         point: &Self::Field,
         max_degree: usize,
-    ) -> KZGResult<Self::Commitment>;
+    ) -> KZGResult<Self::Proof>;
 
     /// Verify an evaluation proof that polynomial inside commitment
     /// evaluates to `value` on input `point `.
@@ -86,9 +99,17 @@ pub trait PolyComScheme: Sized + Eq + PartialEq + Clone {
         degree: usize,
         point: &Self::Field,
         value: &Self::Field,
-        proof: &Self::Commitment,
+        proof: &Self::Proof,
     ) -> KZGResult<()>;
 
+    /// Shrink this to only for verifier use.
+    fn shrink_to_verifier_only(&self) -> KZGResult<Self>;
+}
+
+/// Trait for polynomial commitment scheme whose commitments are homomorphic.
+pub trait HomomorphicPolyComScheme:
+    PolyComScheme<Commitment: HomomorphicPolyComElem<Scalar = <Self as PolyComScheme>::Field>>
+{
     /// Apply blind factors over the vanishing part
     fn apply_blind_factors(
         &self,
@@ -96,6 +117,57 @@ pub trait PolyComScheme: Sized + Eq + PartialEq + Clone {
         blinds: &[Self::Field],
         zeroing_degree: usize,
     ) -> Self::Commitment;
+}
+
+/// Trait for multilinear polynomial commitment scheme.
+pub trait MultilinearPolyComScheme: Sized + Eq + PartialEq + Clone {
+    /// Type of prime field.
+    type Field: PrimeField + Debug + Sync + Send;
+
+    /// Type of commitment produces, need to implement `HomomorphicPolyComElem`.
+    type Commitment: HomomorphicPolyComElem<Scalar = Self::Field>
+        + Debug
+        + Default
+        + PartialEq
+        + Eq
+        + Clone
+        + Sync;
+
+    /// Type of evaluation proof.
+    type Proof: Debug + PartialEq + Eq + Clone + serde::Serialize + serde::de::DeserializeOwned;
+
+    /// Return maximal supported number of variables.
+    fn max_num_vars(&self) -> usize;
+
+    /// Commit to the polynomial, commitment is binding.
+    fn commit(
+        &self,
+        polynomial: &DenseMultilinearPolynomial<Self::Field>,
+    ) -> KZGResult<Self::Commitment>;
+
+    /// Evaluate the polynomial at `point`.
+    fn eval(
+        &self,
+        polynomial: &DenseMultilinearPolynomial<Self::Field>,
+        point: &[Self::Field],
+    ) -> Self::Field;
+
+    /// Evaluate the polynomial at `point` producing a proof for it.
+    fn prove(
+        &self,
+        polynomial: &DenseMultilinearPolynomial<Self::Field>,
+        point: &[Self::Field],
+    ) -> KZGResult<Self::Proof>;
+
+    /// Verify an evaluation proof that polynomial inside commitment
+    /// evaluates to `value` on input `point`.
+    fn verify(
+        &self,
+        commitment: &Self::Commitment,
+        point: &[Self::Field],
+        value: &Self::Field,
+        proof: &Self::Proof,
+    ) -> KZGResult<()>;
 
     /// Shrink this to only for verifier use.
     fn shrink_to_verifier_only(&self) -> KZGResult<Self>;
@@ -0,0 +1,141 @@
+use sha2::{Digest, Sha256};
+
+/// A 32-byte SHA-256 digest.
+pub type Digest32 = [u8; 32];
+
+/// Domain separator of the leaf hashes.
+const LEAF_TAG: u8 = 0;
+
+/// Domain separator of the inner node hashes.
+const NODE_TAG: u8 = 1;
+
+/// A binary Merkle tree over SHA-256, with a power-of-two number of leaves.
+/// Leaves and inner nodes are hashed with distinct tags, so that an inner
+/// node can not be passed off as a leaf.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleTree {
+    /// The layers of the tree, from the leaves up to the root.
+    layers: Vec<Vec<Digest32>>,
+}
+
+/// Hash the content of a leaf.
+pub fn hash_leaf(data: &[u8]) -> Digest32 {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_TAG]);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// Hash two children into their parent.
+fn hash_children(left: &Digest32, right: &Digest32) -> Digest32 {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_TAG]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+impl MerkleTree {
+    /// Build the tree over the `leaves`, hashed with `hash_leaf`.
+    pub fn new(leaves: Vec<Digest32>) -> Self {
+        assert!(leaves.len().is_power_of_two());
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let layer = layers
+                .last()
+                .unwrap()
+                .chunks_exact(2)
+                .map(|pair| hash_children(&pair[0], &pair[1]))
+                .collect();
+            layers.push(layer);
+        }
+        Self { layers }
+    }
+
+    /// Return the root.
+    pub fn root(&self) -> Digest32 {
+        self.layers.last().unwrap()[0]
+    }
+
+    /// Return the authentication path of the `index`-th leaf, from the
+    /// sibling of the leaf up to the children of the root.
+    pub fn open(&self, index: usize) -> Vec<Digest32> {
+        let mut index = index;
+        let mut path = Vec::with_capacity(self.layers.len() - 1);
+        for layer in &self.layers[..self.layers.len() - 1] {
+            path.push(layer[index ^ 1]);
+            index >>= 1;
+        }
+        path
+    }
+
+    /// Verify that `leaf` is the `index`-th leaf of the tree of `root`,
+    /// which has `num_leaves` leaves.
+    pub fn verify(
+        root: &Digest32,
+        num_leaves: usize,
+        index: usize,
+        leaf: &Digest32,
+        path: &[Digest32],
+    ) -> bool {
+        if !num_leaves.is_power_of_two() || path.len() != num_leaves.trailing_zeros() as usize {
+            return false;
+        }
+        let mut index = index;
+        let mut node = *leaf;
+        for sibling in path {
+            node = if index & 1 == 0 {
+                hash_children(&node, sibling)
+            } else {
+                hash_children(sibling, &node)
+            };
+            index >>= 1;
+        }
+        index == 0 && node == *root
+    }
+}
+
+#[cfg(test)]
+mod tests_merkle {
+    use super::{hash_children, hash_leaf, MerkleTree};
+
+    #[test]
+    fn test_merkle_tree() {
+        let leaves: Vec<[u8; 32]> = (0..8u8).map(|i| hash_leaf(&[i])).collect();
+        let tree = MerkleTree::new(leaves.clone());
+        let root = tree.root();
+        for (i, leaf) in leaves.iter().enumerate() {
+            let path = tree.open(i);
+            assert_eq!(path.len(), 3);
+            assert!(MerkleTree::verify(&root, 8, i, leaf, &path));
+            assert!(!MerkleTree::verify(&root, 8, i ^ 1, leaf, &path));
+            assert!(!MerkleTree::verify(&root, 8, i + 8, leaf, &path));
+        }
+        let single = MerkleTree::new(vec![hash_leaf(&[1])]);
+        assert!(MerkleTree::verify(
+            &single.root(),
+            1,
+            0,
+            &hash_leaf(&[1]),
+            &[]
+        ));
+    }
+
+    #[test]
+    fn test_path_length() {
+        let leaves: Vec<[u8; 32]> = (0..8u8).map(|i| hash_leaf(&[i])).collect();
+        let tree = MerkleTree::new(leaves.clone());
+        let root = tree.root();
+        let path = tree.open(0);
+
+        // An inner node opened with a truncated path is not a leaf.
+        let node = hash_children(&leaves[0], &leaves[1]);
+        assert!(!MerkleTree::verify(&root, 8, 0, &node, &path[1..]));
+        assert_ne!(hash_leaf(&[leaves[0], leaves[1]].concat()), node);
+
+        let mut long_path = path.clone();
+        long_path.push(root);
+        assert!(!MerkleTree::verify(&root, 8, 0, &leaves[0], &long_path));
+        assert!(!MerkleTree::verify(&root, 6, 0, &leaves[0], &path));
+    }
+}
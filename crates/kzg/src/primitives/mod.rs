@@ -1,2 +1,5 @@
+pub mod merkle;
+pub mod multilinear;
 pub mod poly;
 pub mod serde;
+pub mod transcript;
@@ -0,0 +1,198 @@
+use ark_ff::PrimeField;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    errs::{KZGError, KZGResult},
+    primitives::serde::{ark_deserialize, ark_serialize},
+};
+
+/// Dense multilinear polynomial, given by its evaluations over the boolean hypercube.
+///
+/// The evaluation at `(b_0, ..., b_{n-1})` is stored at index `sum_j b_j 2^j`,
+/// so that the first variable is the least significant bit of the index.
+/// The number of evaluations is always a power of two.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DenseMultilinearPolynomial<F: PrimeField> {
+    /// Evaluations over the boolean hypercube.
+    evals: Vec<F>,
+}
+
+impl<F: PrimeField> Serialize for DenseMultilinearPolynomial<F> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ark_serialize(&self.evals, serializer)
+    }
+}
+
+impl<'de, F: PrimeField> Deserialize<'de> for DenseMultilinearPolynomial<F> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::from_evals(ark_deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+impl<F: PrimeField> DenseMultilinearPolynomial<F> {
+    /// Build a polynomial from its `2^num_vars` evaluations over the hypercube,
+    /// returning an error if their number is not a power of two.
+    /// # Example
+    /// ```
+    /// use kzg::primitives::multilinear::DenseMultilinearPolynomial;
+    /// use ark_bn254::Fr;
+    /// use ark_ff::{AdditiveGroup, Field};
+    ///
+    /// // f(x_0, x_1) = 1 + x_0 + 2 * x_1
+    /// let one = Fr::ONE;
+    /// let two = one + one;
+    /// let poly = DenseMultilinearPolynomial::from_evals(vec![one, two, one + two, two + two]).unwrap();
+    /// assert_eq!(poly.num_vars(), 2);
+    /// assert_eq!(poly.evaluate(&[Fr::ZERO, one]), one + two);
+    /// assert_eq!(poly.evaluate(&[two, two]), one + two + two + two);
+    /// ```
+    pub fn from_evals(evals: Vec<F>) -> KZGResult<Self> {
+        if !evals.len().is_power_of_two() {
+            return Err(KZGError::DegreeError);
+        }
+        Ok(Self { evals })
+    }
+
+    /// Return the zero polynomial in `num_vars` variables.
+    pub fn zero(num_vars: usize) -> Self {
+        Self {
+            evals: vec![F::ZERO; 1 << num_vars],
+        }
+    }
+
+    /// Return a polynomial in `num_vars` variables with uniformly random evaluations.
+    #[cfg(test)]
+    pub fn random<R: ark_std::rand::RngCore>(prng: &mut R, num_vars: usize) -> Self {
+        Self {
+            evals: (0..1 << num_vars).map(|_| F::rand(prng)).collect(),
+        }
+    }
+
+    /// Return the number of variables.
+    pub fn num_vars(&self) -> usize {
+        self.evals.len().trailing_zeros() as usize
+    }
+
+    /// Return the evaluations reference.
+    pub fn get_evals_ref(&self) -> &[F] {
+        self.evals.as_slice()
+    }
+
+    /// Fix the first variables to `point`, returning a polynomial in the
+    /// remaining variables.
+    pub fn fix_variables(&self, point: &[F]) -> Self {
+        let num_vars = self.num_vars();
+        assert!(point.len() <= num_vars);
+        let mut evals = self.evals.clone();
+        for (i, z) in point.iter().enumerate() {
+            let half = 1 << (num_vars - i - 1);
+            for j in 0..half {
+                let (low, high) = (evals[2 * j], evals[2 * j + 1]);
+                evals[j] = low + *z * (high - low);
+            }
+            evals.truncate(half);
+        }
+        Self { evals }
+    }
+
+    /// Evaluate the polynomial at `point`.
+    pub fn evaluate(&self, point: &[F]) -> F {
+        assert_eq!(point.len(), self.num_vars());
+        self.fix_variables(point).evals[0]
+    }
+
+    /// Add another polynomial with the same number of variables.
+    pub fn add_assign(&mut self, other: &Self) {
+        assert_eq!(self.num_vars(), other.num_vars());
+        for (a, b) in self.evals.iter_mut().zip(&other.evals) {
+            *a += b;
+        }
+    }
+
+    /// Subtract another polynomial with the same number of variables.
+    pub fn sub_assign(&mut self, other: &Self) {
+        assert_eq!(self.num_vars(), other.num_vars());
+        for (a, b) in self.evals.iter_mut().zip(&other.evals) {
+            *a -= b;
+        }
+    }
+
+    /// Multiply the polynomial by a scalar.
+    pub fn mul_scalar_assign(&mut self, scalar: &F) {
+        for a in self.evals.iter_mut() {
+            *a *= scalar;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_multilinear {
+    use super::DenseMultilinearPolynomial;
+    use ark_bn254::Fr;
+    use ark_ff::{AdditiveGroup, Field, UniformRand};
+    use ark_std::test_rng;
+
+    #[test]
+    fn test_evaluate() {
+        let mut prng = test_rng();
+        let num_vars = 4;
+        let poly = DenseMultilinearPolynomial::<Fr>::random(&mut prng, num_vars);
+
+        // The evaluations on the hypercube.
+        for (i, eval) in poly.get_evals_ref().iter().enumerate() {
+            let point: Vec<Fr> = (0..num_vars)
+                .map(|j| if (i >> j) & 1 == 1 { Fr::ONE } else { Fr::ZERO })
+                .collect();
+            assert_eq!(poly.evaluate(&point), *eval);
+        }
+
+        // The multilinear extension: sum_b f(b) eq(b, z).
+        let point: Vec<Fr> = (0..num_vars).map(|_| Fr::rand(&mut prng)).collect();
+        let mut expected = Fr::ZERO;
+        for (i, eval) in poly.get_evals_ref().iter().enumerate() {
+            let mut eq = Fr::ONE;
+            for (j, z) in point.iter().enumerate() {
+                eq *= if (i >> j) & 1 == 1 { *z } else { Fr::ONE - z };
+            }
+            expected += eq * eval;
+        }
+        assert_eq!(poly.evaluate(&point), expected);
+
+        let partial = poly.fix_variables(&point[..1]);
+        assert_eq!(partial.num_vars(), num_vars - 1);
+        assert_eq!(partial.evaluate(&point[1..]), expected);
+    }
+
+    #[test]
+    fn test_from_evals() {
+        let poly = DenseMultilinearPolynomial::from_evals(vec![Fr::ONE]).unwrap();
+        assert_eq!(poly.num_vars(), 0);
+        assert_eq!(poly.evaluate(&[]), Fr::ONE);
+        assert!(DenseMultilinearPolynomial::<Fr>::from_evals(vec![]).is_err());
+        assert!(DenseMultilinearPolynomial::from_evals(vec![Fr::ONE; 3]).is_err());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let mut prng = test_rng();
+        let a = DenseMultilinearPolynomial::<Fr>::random(&mut prng, 3);
+        let b = DenseMultilinearPolynomial::<Fr>::random(&mut prng, 3);
+        let point: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut prng)).collect();
+        let scalar = Fr::rand(&mut prng);
+
+        let mut c = a.clone();
+        c.mul_scalar_assign(&scalar);
+        c.add_assign(&b);
+        assert_eq!(
+            c.evaluate(&point),
+            scalar * a.evaluate(&point) + b.evaluate(&point)
+        );
+        c.sub_assign(&b);
+        assert_eq!(c.evaluate(&point), scalar * a.evaluate(&point));
+        assert_eq!(
+            DenseMultilinearPolynomial::<Fr>::zero(3).evaluate(&point),
+            Fr::ZERO
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalSerialize, Compress};
+use sha2::{Digest, Sha256};
+
+/// A Fiat-Shamir transcript over SHA-256.
+///
+/// Messages are absorbed with their label and length, and every challenge is
+/// squeezed from the running hash, which is then ratcheted with the output.
+#[derive(Clone, Debug)]
+pub struct Transcript {
+    hasher: Sha256,
+}
+
+impl Transcript {
+    /// Start a transcript bound to the domain separator `label`.
+    pub fn new(label: &[u8]) -> Self {
+        let mut transcript = Self {
+            hasher: Sha256::new(),
+        };
+        transcript.append_message(b"dom-sep", label);
+        transcript
+    }
+
+    /// Absorb a labelled message.
+    pub fn append_message(&mut self, label: &[u8], message: &[u8]) {
+        self.hasher.update((label.len() as u64).to_le_bytes());
+        self.hasher.update(label);
+        self.hasher.update((message.len() as u64).to_le_bytes());
+        self.hasher.update(message);
+    }
+
+    /// Absorb the compressed serialization of a field element or group element.
+    pub fn append_serializable<T: CanonicalSerialize>(&mut self, label: &[u8], elem: &T) {
+        let mut buf = Vec::new();
+        elem.serialize_with_mode(&mut buf, Compress::Yes).unwrap();
+        self.append_message(label, &buf);
+    }
+
+    /// Squeeze 32 bytes of challenge.
+    pub fn challenge_bytes(&mut self, label: &[u8]) -> [u8; 32] {
+        self.append_message(b"challenge", label);
+        let output: [u8; 32] = self.hasher.clone().finalize().into();
+        self.hasher.update(output);
+        output
+    }
+
+    /// Squeeze a challenge field element, from 64 bytes reduced modulo the
+    /// field order so that the bias is negligible.
+    pub fn challenge_scalar<F: PrimeField>(&mut self, label: &[u8]) -> F {
+        let mut bytes = self.challenge_bytes(label).to_vec();
+        bytes.extend(self.challenge_bytes(label));
+        F::from_le_bytes_mod_order(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests_transcript {
+    use super::Transcript;
+    use ark_bn254::Fr;
+
+    #[test]
+    fn test_transcript() {
+        let mut t1 = Transcript::new(b"test");
+        let mut t2 = Transcript::new(b"test");
+        t1.append_message(b"m", b"hello");
+        t2.append_message(b"m", b"hello");
+        let c1: Fr = t1.challenge_scalar(b"c");
+        assert_eq!(c1, t2.challenge_scalar::<Fr>(b"c"));
+        // Successive challenges differ.
+        assert_ne!(c1, t1.challenge_scalar::<Fr>(b"c"));
+
+        // Domain separation and message framing.
+        let mut t3 = Transcript::new(b"other");
+        t3.append_message(b"m", b"hello");
+        assert_ne!(c1, t3.challenge_scalar::<Fr>(b"c"));
+        let mut t4 = Transcript::new(b"test");
+        t4.append_message(b"mh", b"ello");
+        assert_ne!(c1, t4.challenge_scalar::<Fr>(b"c"));
+    }
+}
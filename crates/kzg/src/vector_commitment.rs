@@ -0,0 +1,422 @@
+//! KZG as a vector commitment: the vector `v` of size `n` is committed as the
+//! polynomial `phi` with `phi(w^i) = v_i` on the domain of the `n`-th roots
+//! of unity, so that the commitment is an MSM with the Lagrange basis
+//! `[L_i(tau)]` and changing one element updates it in constant time.
+//!
+//! Proofs are updated in constant time as in Tomescu et al., "Aggregatable
+//! subvector commitments for stateless cryptocurrencies", with `A = X^n - 1`,
+//! `a_i = [A(tau) / (tau - w^i)]` and `u_i = [(L_i(tau) - 1) / (tau - w^i)]`:
+//! when `v_j` changes by `delta`, the proof of `j` moves by `delta * u_j` and
+//! the proof of `i != j` by `delta * (a_j - a_i) / (A'(w^j) * (w^j - w^i))`.
+
+use ark_ec::{pairing::Pairing, CurveGroup, VariableBaseMSM};
+use ark_ff::Field;
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+
+use crate::{
+    backend::kzg::{
+        KZGCommitment, KZGCommitmentScheme, KZGMultiPointProof, KZGOpenProof, KZGOpeningClaim,
+    },
+    errs::{KZGError, KZGResult},
+    primitives::{poly::FpPolynomial, transcript::Transcript},
+    PolyComScheme,
+};
+
+/// A KZG vector commitment scheme for vectors of a fixed size.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VectorCommitment<P: Pairing> {
+    pcs: KZGCommitmentScheme<P>,
+    domain: Radix2EvaluationDomain<P::ScalarField>,
+    /// The Lagrange basis `[L_i(tau)]_1`.
+    lagrange_srs: Vec<P::G1>,
+    /// `[A(tau) / (tau - w^i)]_1`.
+    vanishing_quotients: Vec<P::G1>,
+    /// `[(L_i(tau) - 1) / (tau - w^i)]_1`.
+    lagrange_quotients: Vec<P::G1>,
+}
+
+impl<P: Pairing> VectorCommitment<P> {
+    /// Precompute the Lagrange basis and the update keys for vectors of
+    /// `size` elements, a power of two not above the degree bound of `pcs`.
+    pub fn new(pcs: KZGCommitmentScheme<P>, size: usize) -> KZGResult<Self> {
+        if !size.is_power_of_two() {
+            return Err(KZGError::DomainError);
+        }
+        if pcs.public_parameter_group_1.len() < size {
+            return Err(KZGError::DegreeError);
+        }
+        let domain =
+            Radix2EvaluationDomain::<P::ScalarField>::new(size).ok_or(KZGError::DomainError)?;
+        let srs = &pcs.public_parameter_group_1[..size];
+
+        // L_i(tau) = (1 / n) sum_k w^(-ik) tau^k
+        let lagrange_srs = domain.ifft(srs);
+
+        // A(X) / (X - w^i) = sum_s w^(is) X^(n - 1 - s)
+        let reversed: Vec<P::G1> = srs.iter().rev().copied().collect();
+        let vanishing_quotients = domain.fft(&reversed);
+
+        // (L_i(X) - 1) / (X - w^i) = (w^(-i) / n) sum_t (n - 1 - t) w^(-it) X^t
+        let weighted: Vec<P::G1> = srs
+            .iter()
+            .enumerate()
+            .map(|(t, s)| *s * P::ScalarField::from((size - 1 - t) as u64))
+            .collect();
+        let lagrange_quotients = domain
+            .ifft(&weighted)
+            .into_iter()
+            .enumerate()
+            .map(|(i, u)| u * domain.element(i).inverse().unwrap())
+            .collect();
+
+        Ok(Self {
+            pcs,
+            domain,
+            lagrange_srs,
+            vanishing_quotients,
+            lagrange_quotients,
+        })
+    }
+
+    /// Return the size of the vectors.
+    pub fn size(&self) -> usize {
+        self.domain.size()
+    }
+
+    /// Return the point `w^index` of the domain holding the element `index`.
+    pub fn point(&self, index: usize) -> P::ScalarField {
+        self.domain.element(index)
+    }
+
+    /// Return the Lagrange basis of the SRS.
+    pub fn lagrange_srs(&self) -> &[P::G1] {
+        &self.lagrange_srs
+    }
+
+    /// Return the polynomial interpolating `values`, padded with zeros.
+    pub fn interpolate(
+        &self,
+        values: &[P::ScalarField],
+    ) -> KZGResult<FpPolynomial<P::ScalarField>> {
+        if values.len() > self.size() {
+            return Err(KZGError::DegreeError);
+        }
+        Ok(FpPolynomial::ifft_with_domain(&self.domain, values))
+    }
+
+    /// Commit to `values`, padded with zeros, with an MSM over the Lagrange basis.
+    pub fn commit(&self, values: &[P::ScalarField]) -> KZGResult<KZGCommitment<P::G1>> {
+        if values.len() > self.size() {
+            return Err(KZGError::DegreeError);
+        }
+        let bases = P::G1::normalize_batch(&self.lagrange_srs[..values.len()]);
+        Ok(KZGCommitment(P::G1::msm(&bases, values).unwrap()))
+    }
+
+    /// Prove the element at `index` of `values`.
+    pub fn open(&self, values: &[P::ScalarField], index: usize) -> KZGResult<KZGOpenProof<P::G1>> {
+        self.check_index(index)?;
+        let poly = self.interpolate(values)?;
+        let proof = self.pcs.prove(&poly, &self.point(index), self.size() - 1)?;
+        Ok(KZGOpenProof(proof.0))
+    }
+
+    /// Verify that the element at `index` of the committed vector is `value`.
+    pub fn verify(
+        &self,
+        commitment: &KZGCommitment<P::G1>,
+        index: usize,
+        value: &P::ScalarField,
+        proof: &KZGOpenProof<P::G1>,
+    ) -> KZGResult<()> {
+        self.check_index(index)?;
+        self.pcs.verify(
+            commitment,
+            self.size() - 1,
+            &self.point(index),
+            value,
+            &KZGCommitment(proof.0),
+        )
+    }
+
+    /// Prove the elements at `indices` of `values` with a single proof, the
+    /// commitment to `(phi - I) / Z`, where `I` interpolates the elements on
+    /// their points and `Z` vanishes on them.
+    pub fn open_batch(
+        &self,
+        values: &[P::ScalarField],
+        indices: &[usize],
+    ) -> KZGResult<KZGOpenProof<P::G1>> {
+        let points = self.batch_points(indices)?;
+        let poly = self.interpolate(values)?;
+        let evals: Vec<P::ScalarField> = points.iter().map(|x| poly.eval(x)).collect();
+        let numerator = poly.sub(&lagrange_interpolate(&points, &evals));
+        let (quotient, remainder) = numerator.div_rem(&FpPolynomial::from_zeroes(&points));
+        if !remainder.is_zero() {
+            return Err(KZGError::PCSProveEvalError);
+        }
+        let proof = self.pcs.commit(&quotient)?;
+        Ok(KZGOpenProof(proof.0))
+    }
+
+    /// Verify that the elements at `indices` of the committed vector are
+    /// `values`: `e(C - [I(tau)]_1, [1]_2) = e(proof, [Z(tau)]_2)`, which needs
+    /// the powers of the secret in G2 up to the number of indices.
+    pub fn verify_batch(
+        &self,
+        commitment: &KZGCommitment<P::G1>,
+        indices: &[usize],
+        values: &[P::ScalarField],
+        proof: &KZGOpenProof<P::G1>,
+    ) -> KZGResult<()> {
+        let points = self.batch_points(indices)?;
+        if values.len() != points.len() {
+            return Err(KZGError::IndexError);
+        }
+        let g2_powers = &self.pcs.public_parameter_group_2;
+        if g2_powers.len() <= points.len() {
+            return Err(KZGError::DegreeError);
+        }
+        let vanishing = FpPolynomial::from_zeroes(&points);
+        let vanishing_g2 = P::G2::msm(
+            &P::G2::normalize_batch(&g2_powers[..vanishing.get_coefs_ref().len()]),
+            vanishing.get_coefs_ref(),
+        )
+        .unwrap();
+        let interpolation = self.pcs.commit(&lagrange_interpolate(&points, values))?;
+
+        let lhs = P::pairing(commitment.0 - interpolation.0, g2_powers[0]);
+        let rhs = P::pairing(proof.0, vanishing_g2);
+        if lhs == rhs {
+            Ok(())
+        } else {
+            Err(KZGError::PCSProveEvalError)
+        }
+    }
+
+    /// Return the claim that the element at `index` of the vector inside
+    /// `commitment` is `value`.
+    pub fn claim(
+        &self,
+        commitment: &KZGCommitment<P::G1>,
+        index: usize,
+        value: &P::ScalarField,
+    ) -> KZGResult<KZGOpeningClaim<P>> {
+        self.check_index(index)?;
+        Ok(KZGOpeningClaim::new(
+            commitment.clone(),
+            self.point(index),
+            *value,
+        ))
+    }
+
+    /// Prove claims on several vectors with a single proof, `vectors[i]`
+    /// being the vector of `claims[i]`, by reducing them to an opening at a
+    /// random point.
+    pub fn open_multi_point(
+        &self,
+        transcript: &mut Transcript,
+        vectors: &[&[P::ScalarField]],
+        claims: &[KZGOpeningClaim<P>],
+    ) -> KZGResult<KZGMultiPointProof<P::G1>> {
+        let polys = vectors
+            .iter()
+            .map(|values| self.interpolate(values))
+            .collect::<KZGResult<Vec<_>>>()?;
+        self.pcs.prove_multi_point(transcript, &polys, claims)
+    }
+
+    /// Verify a proof of `open_multi_point`, with the powers `[1]_2` and
+    /// `[tau]_2` only.
+    pub fn verify_multi_point(
+        &self,
+        transcript: &mut Transcript,
+        claims: &[KZGOpeningClaim<P>],
+        proof: &KZGMultiPointProof<P::G1>,
+    ) -> KZGResult<()> {
+        self.pcs.verify_multi_point(transcript, claims, proof)
+    }
+
+    /// Update `commitment` when the element at `index` changes from
+    /// `old_value` to `new_value`, in constant time.
+    pub fn update_commitment(
+        &self,
+        commitment: &KZGCommitment<P::G1>,
+        index: usize,
+        old_value: &P::ScalarField,
+        new_value: &P::ScalarField,
+    ) -> KZGResult<KZGCommitment<P::G1>> {
+        self.check_index(index)?;
+        let delta = *new_value - old_value;
+        Ok(KZGCommitment(
+            commitment.0 + self.lagrange_srs[index] * delta,
+        ))
+    }
+
+    /// Update the proof of the element at `proof_index` when the element at
+    /// `index` changes from `old_value` to `new_value`, in constant time.
+    pub fn update_proof(
+        &self,
+        proof: &KZGOpenProof<P::G1>,
+        proof_index: usize,
+        index: usize,
+        old_value: &P::ScalarField,
+        new_value: &P::ScalarField,
+    ) -> KZGResult<KZGOpenProof<P::G1>> {
+        self.check_index(proof_index)?;
+        self.check_index(index)?;
+        let delta = *new_value - old_value;
+        if proof_index == index {
+            return Ok(KZGOpenProof(
+                proof.0 + self.lagrange_quotients[index] * delta,
+            ));
+        }
+        // A'(w^j) = n * w^(-j)
+        let point = self.point(index);
+        let denominator = P::ScalarField::from(self.size() as u64)
+            * (point - self.point(proof_index))
+            * point.inverse().unwrap();
+        let diff = self.vanishing_quotients[index] - self.vanishing_quotients[proof_index];
+        Ok(KZGOpenProof(
+            proof.0 + diff * (delta * denominator.inverse().unwrap()),
+        ))
+    }
+
+    fn check_index(&self, index: usize) -> KZGResult<()> {
+        if index < self.size() {
+            Ok(())
+        } else {
+            Err(KZGError::IndexError)
+        }
+    }
+
+    fn batch_points(&self, indices: &[usize]) -> KZGResult<Vec<P::ScalarField>> {
+        let mut sorted = indices.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+        if indices.is_empty() || sorted.len() != indices.len() {
+            return Err(KZGError::IndexError);
+        }
+        indices
+            .iter()
+            .map(|index| self.check_index(*index).map(|_| self.point(*index)))
+            .collect()
+    }
+}
+
+/// Return the polynomial of degree less than `points.len()` taking `evals`
+/// at `points`, by Lagrange interpolation.
+fn lagrange_interpolate<F: ark_ff::PrimeField>(points: &[F], evals: &[F]) -> FpPolynomial<F> {
+    let mut result = FpPolynomial::zero();
+    for (i, (x_i, y_i)) in points.iter().zip(evals).enumerate() {
+        let others: Vec<F> = points
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, x_j)| *x_j)
+            .collect();
+        let denominator = others.iter().fold(F::ONE, |acc, x_j| acc * (*x_i - x_j));
+        let basis = FpPolynomial::from_zeroes(&others);
+        result.add_assign(&basis.mul_scalar(&(*y_i * denominator.inverse().unwrap())));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests_vector_commitment {
+    use super::VectorCommitment;
+    use crate::{backend::kzg::KZGCommitmentScheme, errs::KZGError, PolyComScheme};
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::UniformRand;
+    use ark_std::test_rng;
+
+    const SIZE: usize = 16;
+
+    fn setup() -> (VectorCommitment<Bn254>, Vec<Fr>) {
+        let mut prng = test_rng();
+        let pcs = KZGCommitmentScheme::new_with_g2_degree(SIZE - 1, 4, &mut prng);
+        let vc = VectorCommitment::new(pcs, SIZE).unwrap();
+        let values = (0..SIZE).map(|_| Fr::rand(&mut prng)).collect();
+        (vc, values)
+    }
+
+    #[test]
+    fn test_commit_open_verify() {
+        let (vc, values) = setup();
+        let commitment = vc.commit(&values).unwrap();
+        let poly = vc.interpolate(&values).unwrap();
+        assert_eq!(commitment, vc.pcs.commit(&poly).unwrap());
+
+        for (index, value) in values.iter().enumerate() {
+            let proof = vc.open(&values, index).unwrap();
+            vc.verify(&commitment, index, value, &proof).unwrap();
+            assert!(vc
+                .verify(&commitment, index, &(*value + Fr::from(1u64)), &proof)
+                .is_err());
+            assert!(vc
+                .verify(&commitment, (index + 1) % SIZE, value, &proof)
+                .is_err());
+        }
+        assert!(matches!(vc.open(&values, SIZE), Err(KZGError::IndexError)));
+
+        // Shorter vectors are padded with zeros.
+        let commitment = vc.commit(&values[..3]).unwrap();
+        let proof = vc.open(&values[..3], 7).unwrap();
+        vc.verify(&commitment, 7, &Fr::from(0u64), &proof).unwrap();
+    }
+
+    #[test]
+    fn test_open_batch() {
+        let (vc, values) = setup();
+        let commitment = vc.commit(&values).unwrap();
+        for indices in [vec![3], vec![0, 5], vec![15, 2, 9, 4]] {
+            let evals: Vec<Fr> = indices.iter().map(|i| values[*i]).collect();
+            let proof = vc.open_batch(&values, &indices).unwrap();
+            vc.verify_batch(&commitment, &indices, &evals, &proof)
+                .unwrap();
+
+            let mut wrong = evals.clone();
+            wrong[0] += Fr::from(1u64);
+            assert!(vc
+                .verify_batch(&commitment, &indices, &wrong, &proof)
+                .is_err());
+        }
+
+        // Too many indices for the G2 powers, duplicated or out of range indices.
+        let indices = [0, 1, 2, 3, 4];
+        let evals: Vec<Fr> = indices.iter().map(|i| values[*i]).collect();
+        let proof = vc.open_batch(&values, &indices).unwrap();
+        assert!(vc
+            .verify_batch(&commitment, &indices, &evals, &proof)
+            .is_err());
+        assert!(vc.open_batch(&values, &[1, 1]).is_err());
+        assert!(vc.open_batch(&values, &[SIZE]).is_err());
+        assert!(vc.open_batch(&values, &[]).is_err());
+    }
+
+    #[test]
+    fn test_updates() {
+        let (vc, mut values) = setup();
+        let mut prng = test_rng();
+        let mut commitment = vc.commit(&values).unwrap();
+        let mut proofs: Vec<_> = (0..SIZE).map(|i| vc.open(&values, i).unwrap()).collect();
+
+        for index in [0, 7, 7, SIZE - 1] {
+            let new_value = Fr::rand(&mut prng);
+            commitment = vc
+                .update_commitment(&commitment, index, &values[index], &new_value)
+                .unwrap();
+            for (proof_index, proof) in proofs.iter_mut().enumerate() {
+                *proof = vc
+                    .update_proof(proof, proof_index, index, &values[index], &new_value)
+                    .unwrap();
+            }
+            values[index] = new_value;
+
+            assert_eq!(commitment, vc.commit(&values).unwrap());
+            for (proof_index, proof) in proofs.iter().enumerate() {
+                assert_eq!(*proof, vc.open(&values, proof_index).unwrap());
+            }
+        }
+    }
+}
@@ -0,0 +1,674 @@
+//! A Verkle trie of width 256 over KZG vector commitments.
+//!
+//! Keys and values are 32 bytes. An internal node commits to the hashes of
+//! its 256 children, indexed by the next byte of the key, with zero for empty
+//! children. A leaf sits at the shortest prefix of its key that no other key
+//! shares and commits to `[1, key_lo, key_hi, value_lo, value_hi]`, the
+//! halves being 16-byte little-endian integers. The hash of a child is
+//! SHA-256 of a node type tag and its compressed commitment, reduced in the
+//! scalar field, so that a parent also binds the type of its children.
+//!
+//! Deleting collapses the internal nodes left with a single leaf, so the root
+//! commitment only depends on the set of entries. A multiproof for a set of
+//! keys opens every node on their paths once, at all the indices the keys
+//! need, and reduces all these openings to a single opening at a random
+//! point, as in the Verkle multiproofs of Ethereum, so that besides the
+//! opened values the proof is two group elements.
+
+use ark_ec::pairing::Pairing;
+use ark_ff::{BigInteger, Field, PrimeField};
+use ark_serialize::{CanonicalSerialize, Compress};
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    Zero,
+};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    backend::kzg::{KZGCommitment, KZGMultiPointProof},
+    errs::{KZGError, KZGResult},
+    primitives::transcript::Transcript,
+    vector_commitment::VectorCommitment,
+};
+
+/// The number of children of an internal node.
+pub const VERKLE_WIDTH: usize = 256;
+
+/// Number of elements committed in a leaf.
+const LEAF_SIZE: usize = 5;
+
+/// A key of the trie.
+pub type VerkleKey = [u8; 32];
+
+/// A value of the trie.
+pub type VerkleValue = [u8; 32];
+
+/// The type of a node, bound in the hash of its commitment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerkleNodeKind {
+    /// An internal node, committing to the hashes of its children.
+    Internal,
+    /// A leaf, committing to a key and its value.
+    Leaf,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct InternalNode<P: Pairing> {
+    children: BTreeMap<u8, Node<P>>,
+    commitment: KZGCommitment<P::G1>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct LeafNode<P: Pairing> {
+    key: VerkleKey,
+    value: VerkleValue,
+    commitment: KZGCommitment<P::G1>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Node<P: Pairing> {
+    Internal(InternalNode<P>),
+    Leaf(LeafNode<P>),
+}
+
+/// The opening of a node in a Verkle proof.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerkleNodeProof<P: Pairing> {
+    /// The key prefix leading to the node, empty for the root.
+    pub path: Vec<u8>,
+    /// The type of the node.
+    pub kind: VerkleNodeKind,
+    /// The commitment of the node.
+    pub commitment: KZGCommitment<P::G1>,
+    /// The opened indices.
+    pub indices: Vec<usize>,
+    /// The committed values at the opened indices.
+    pub values: Vec<P::ScalarField>,
+}
+
+/// A multiproof of the values of a set of keys, present or absent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerkleProof<P: Pairing> {
+    /// The openings of the nodes on the paths of the keys, ordered by path.
+    pub nodes: Vec<VerkleNodeProof<P>>,
+    /// The proof of all the openings.
+    pub proof: KZGMultiPointProof<P::G1>,
+}
+
+/// A Verkle trie.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerkleTree<P: Pairing> {
+    vc: VectorCommitment<P>,
+    root: InternalNode<P>,
+}
+
+impl<P: Pairing> VerkleTree<P> {
+    /// Create an empty trie over a vector commitment of size `VERKLE_WIDTH`.
+    pub fn new(vc: VectorCommitment<P>) -> KZGResult<Self> {
+        if vc.size() != VERKLE_WIDTH {
+            return Err(KZGError::DomainError);
+        }
+        Ok(Self {
+            vc,
+            root: InternalNode::empty(),
+        })
+    }
+
+    /// Return the vector commitment scheme of the nodes.
+    pub fn vector_commitment(&self) -> &VectorCommitment<P> {
+        &self.vc
+    }
+
+    /// Return the root commitment.
+    pub fn root(&self) -> KZGCommitment<P::G1> {
+        self.root.commitment.clone()
+    }
+
+    /// Return the value of `key`, if any.
+    pub fn get(&self, key: &VerkleKey) -> Option<VerkleValue> {
+        let mut node = &self.root;
+        for byte in key {
+            match node.children.get(byte)? {
+                Node::Internal(child) => node = child,
+                Node::Leaf(leaf) => return (leaf.key == *key).then_some(leaf.value),
+            }
+        }
+        None
+    }
+
+    /// Insert or update the value of `key`, updating the commitments on its path.
+    pub fn insert(&mut self, key: &VerkleKey, value: &VerkleValue) -> KZGResult<()> {
+        self.root.insert(&self.vc, 0, key, value)
+    }
+
+    /// Remove `key`, returning its value if it was present.
+    pub fn remove(&mut self, key: &VerkleKey) -> KZGResult<Option<VerkleValue>> {
+        self.root.remove(&self.vc, 0, key)
+    }
+
+    /// Prove the values of `keys`, present or absent.
+    pub fn prove(&self, keys: &[VerkleKey]) -> KZGResult<VerkleProof<P>> {
+        let mut openings: BTreeMap<Vec<u8>, (&Node<P>, BTreeSet<usize>)> = BTreeMap::new();
+        let mut root_indices = BTreeSet::new();
+        for key in keys {
+            let mut node = &self.root;
+            let mut depth = 0;
+            let mut indices = &mut root_indices;
+            loop {
+                let byte = key[depth];
+                indices.insert(byte as usize);
+                let path = key[..depth + 1].to_vec();
+                match node.children.get(&byte) {
+                    None => break,
+                    Some(child @ Node::Leaf(_)) => {
+                        openings
+                            .entry(path)
+                            .or_insert_with(|| (child, BTreeSet::new()))
+                            .1
+                            .extend(0..LEAF_SIZE);
+                        break;
+                    }
+                    Some(child @ Node::Internal(internal)) => {
+                        indices = &mut openings
+                            .entry(path)
+                            .or_insert_with(|| (child, BTreeSet::new()))
+                            .1;
+                        node = internal;
+                        depth += 1;
+                    }
+                }
+            }
+        }
+
+        let mut nodes = Vec::with_capacity(openings.len() + 1);
+        let mut vectors = Vec::with_capacity(openings.len() + 1);
+        if !root_indices.is_empty() {
+            let (node, vector) = Self::open_node(vec![], &self.root.as_node_ref(), &root_indices);
+            nodes.push(node);
+            vectors.push(vector);
+        }
+        for (path, (node, indices)) in openings {
+            let (node, vector) = Self::open_node(path, &NodeRef::from(node), &indices);
+            nodes.push(node);
+            vectors.push(vector);
+        }
+
+        let mut vector_refs = vec![];
+        let mut claims = vec![];
+        for (node, vector) in nodes.iter().zip(&vectors) {
+            for (index, value) in node.indices.iter().zip(&node.values) {
+                vector_refs.push(vector.as_slice());
+                claims.push(self.vc.claim(&node.commitment, *index, value)?);
+            }
+        }
+        let mut transcript = Transcript::new(b"verkle-multiproof");
+        let proof = self
+            .vc
+            .open_multi_point(&mut transcript, &vector_refs, &claims)?;
+        Ok(VerkleProof { nodes, proof })
+    }
+
+    /// Verify that `values` are the values of `keys`, `None` for the absent
+    /// ones, in the trie of root commitment `root`.
+    pub fn verify(
+        vc: &VectorCommitment<P>,
+        root: &KZGCommitment<P::G1>,
+        keys: &[VerkleKey],
+        values: &[Option<VerkleValue>],
+        proof: &VerkleProof<P>,
+    ) -> KZGResult<()> {
+        if keys.len() != values.len() {
+            return Err(KZGError::VerkleProofError);
+        }
+        let mut nodes = BTreeMap::new();
+        let mut claims = vec![];
+        for node in &proof.nodes {
+            if node.indices.len() != node.values.len() {
+                return Err(KZGError::VerkleProofError);
+            }
+            for (index, value) in node.indices.iter().zip(&node.values) {
+                claims.push(vc.claim(&node.commitment, *index, value)?);
+            }
+            if nodes.insert(node.path.as_slice(), node).is_some() {
+                return Err(KZGError::VerkleProofError);
+            }
+        }
+        let mut transcript = Transcript::new(b"verkle-multiproof");
+        vc.verify_multi_point(&mut transcript, &claims, &proof.proof)?;
+
+        for (key, value) in keys.iter().zip(values) {
+            let root_path: &[u8] = &[];
+            let mut node = *nodes.get(root_path).ok_or(KZGError::VerkleProofError)?;
+            if node.kind != VerkleNodeKind::Internal || node.commitment != *root {
+                return Err(KZGError::VerkleProofError);
+            }
+            let mut depth = 0;
+            let found = loop {
+                match node.kind {
+                    VerkleNodeKind::Internal => {
+                        let byte = *key.get(depth).ok_or(KZGError::VerkleProofError)?;
+                        let hash = opened_value(node, byte as usize)?;
+                        if hash.is_zero() {
+                            break None;
+                        }
+                        let child = *nodes
+                            .get(&key[..depth + 1])
+                            .ok_or(KZGError::VerkleProofError)?;
+                        if hash_commitment::<P>(child.kind, &child.commitment) != hash {
+                            return Err(KZGError::VerkleProofError);
+                        }
+                        node = child;
+                        depth += 1;
+                    }
+                    VerkleNodeKind::Leaf => {
+                        let leaf: Vec<P::ScalarField> = (0..LEAF_SIZE)
+                            .map(|i| opened_value(node, i))
+                            .collect::<KZGResult<_>>()?;
+                        if leaf[0] != P::ScalarField::ONE {
+                            return Err(KZGError::VerkleProofError);
+                        }
+                        let leaf_key = from_halves(&leaf[1], &leaf[2]);
+                        if leaf_key[..depth] != key[..depth] {
+                            return Err(KZGError::VerkleProofError);
+                        }
+                        break (leaf_key == *key).then(|| from_halves(&leaf[3], &leaf[4]));
+                    }
+                }
+            };
+            if found != *value {
+                return Err(KZGError::VerkleProofError);
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the opening of `node` at `indices` and its committed vector.
+    fn open_node(
+        path: Vec<u8>,
+        node: &NodeRef<'_, P>,
+        indices: &BTreeSet<usize>,
+    ) -> (VerkleNodeProof<P>, Vec<P::ScalarField>) {
+        let (kind, commitment, vector) = match node {
+            NodeRef::Internal(internal) => (
+                VerkleNodeKind::Internal,
+                &internal.commitment,
+                internal.vector(),
+            ),
+            NodeRef::Leaf(leaf) => (VerkleNodeKind::Leaf, &leaf.commitment, leaf.vector()),
+        };
+        let indices: Vec<usize> = indices.iter().copied().collect();
+        let node = VerkleNodeProof {
+            path,
+            kind,
+            commitment: commitment.clone(),
+            values: indices.iter().map(|i| vector[*i]).collect(),
+            indices,
+        };
+        (node, vector)
+    }
+}
+
+enum NodeRef<'a, P: Pairing> {
+    Internal(&'a InternalNode<P>),
+    Leaf(&'a LeafNode<P>),
+}
+
+impl<'a, P: Pairing> From<&'a Node<P>> for NodeRef<'a, P> {
+    fn from(node: &'a Node<P>) -> Self {
+        match node {
+            Node::Internal(internal) => NodeRef::Internal(internal),
+            Node::Leaf(leaf) => NodeRef::Leaf(leaf),
+        }
+    }
+}
+
+impl<P: Pairing> Node<P> {
+    fn hash(&self) -> P::ScalarField {
+        match self {
+            Node::Internal(internal) => {
+                hash_commitment::<P>(VerkleNodeKind::Internal, &internal.commitment)
+            }
+            Node::Leaf(leaf) => hash_commitment::<P>(VerkleNodeKind::Leaf, &leaf.commitment),
+        }
+    }
+}
+
+impl<P: Pairing> InternalNode<P> {
+    fn empty() -> Self {
+        Self {
+            children: BTreeMap::new(),
+            commitment: KZGCommitment(P::G1::zero()),
+        }
+    }
+
+    fn as_node_ref(&self) -> NodeRef<'_, P> {
+        NodeRef::Internal(self)
+    }
+
+    fn child_hash(&self, byte: u8) -> P::ScalarField {
+        self.children
+            .get(&byte)
+            .map_or(P::ScalarField::zero(), Node::hash)
+    }
+
+    fn vector(&self) -> Vec<P::ScalarField> {
+        (0..VERKLE_WIDTH)
+            .map(|i| self.child_hash(i as u8))
+            .collect()
+    }
+
+    /// Apply `update` to the child at `byte` and update the commitment with
+    /// the change of its hash.
+    fn update_child<T>(
+        &mut self,
+        vc: &VectorCommitment<P>,
+        byte: u8,
+        update: impl FnOnce(&mut Self) -> KZGResult<T>,
+    ) -> KZGResult<T> {
+        let old_hash = self.child_hash(byte);
+        let result = update(self)?;
+        let new_hash = self.child_hash(byte);
+        self.commitment =
+            vc.update_commitment(&self.commitment, byte as usize, &old_hash, &new_hash)?;
+        Ok(result)
+    }
+
+    fn insert(
+        &mut self,
+        vc: &VectorCommitment<P>,
+        depth: usize,
+        key: &VerkleKey,
+        value: &VerkleValue,
+    ) -> KZGResult<()> {
+        let byte = key[depth];
+        self.update_child(vc, byte, |node| {
+            match node.children.remove(&byte) {
+                None => {
+                    node.children
+                        .insert(byte, Node::Leaf(LeafNode::new(vc, key, value)?));
+                }
+                Some(Node::Leaf(mut leaf)) if leaf.key == *key => {
+                    leaf.set_value(vc, value)?;
+                    node.children.insert(byte, Node::Leaf(leaf));
+                }
+                Some(Node::Leaf(leaf)) => {
+                    // Split: both keys go below a new internal node.
+                    let mut child = InternalNode::empty();
+                    let leaf_byte = leaf.key[depth + 1];
+                    child.update_child(vc, leaf_byte, |child| {
+                        child.children.insert(leaf_byte, Node::Leaf(leaf));
+                        Ok(())
+                    })?;
+                    child.insert(vc, depth + 1, key, value)?;
+                    node.children.insert(byte, Node::Internal(child));
+                }
+                Some(Node::Internal(mut child)) => {
+                    let result = child.insert(vc, depth + 1, key, value);
+                    node.children.insert(byte, Node::Internal(child));
+                    result?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn remove(
+        &mut self,
+        vc: &VectorCommitment<P>,
+        depth: usize,
+        key: &VerkleKey,
+    ) -> KZGResult<Option<VerkleValue>> {
+        let byte = key[depth];
+        match self.children.get(&byte) {
+            None => return Ok(None),
+            Some(Node::Leaf(leaf)) if leaf.key != *key => return Ok(None),
+            _ => {}
+        }
+        self.update_child(vc, byte, |node| match node.children.remove(&byte) {
+            Some(Node::Leaf(leaf)) => Ok(Some(leaf.value)),
+            Some(Node::Internal(mut child)) => {
+                let removed = child.remove(vc, depth + 1, key);
+                // Collapse a node left with a single leaf.
+                let collapsed = match child.children.first_key_value() {
+                    Some((_, Node::Leaf(_))) if child.children.len() == 1 => {
+                        child.children.pop_first().unwrap().1
+                    }
+                    _ => Node::Internal(child),
+                };
+                node.children.insert(byte, collapsed);
+                removed
+            }
+            None => Ok(None),
+        })
+    }
+}
+
+impl<P: Pairing> LeafNode<P> {
+    fn new(vc: &VectorCommitment<P>, key: &VerkleKey, value: &VerkleValue) -> KZGResult<Self> {
+        let mut leaf = Self {
+            key: *key,
+            value: *value,
+            commitment: KZGCommitment(P::G1::zero()),
+        };
+        leaf.commitment = vc.commit(&leaf.vector())?;
+        Ok(leaf)
+    }
+
+    fn vector(&self) -> Vec<P::ScalarField> {
+        let (key_lo, key_hi) = to_halves(&self.key);
+        let (value_lo, value_hi) = to_halves(&self.value);
+        vec![P::ScalarField::ONE, key_lo, key_hi, value_lo, value_hi]
+    }
+
+    fn set_value(&mut self, vc: &VectorCommitment<P>, value: &VerkleValue) -> KZGResult<()> {
+        let old = self.vector();
+        self.value = *value;
+        let new = self.vector();
+        for i in 3..LEAF_SIZE {
+            self.commitment = vc.update_commitment(&self.commitment, i, &old[i], &new[i])?;
+        }
+        Ok(())
+    }
+}
+
+/// Return the opened value at `index` of a node proof.
+fn opened_value<P: Pairing>(node: &VerkleNodeProof<P>, index: usize) -> KZGResult<P::ScalarField> {
+    node.indices
+        .iter()
+        .position(|i| *i == index)
+        .map(|i| node.values[i])
+        .ok_or(KZGError::VerkleProofError)
+}
+
+/// Hash a node commitment with its type into the scalar field.
+fn hash_commitment<P: Pairing>(
+    kind: VerkleNodeKind,
+    commitment: &KZGCommitment<P::G1>,
+) -> P::ScalarField {
+    let mut bytes = Vec::new();
+    commitment
+        .0
+        .serialize_with_mode(&mut bytes, Compress::Yes)
+        .unwrap();
+    let mut hasher = Sha256::new();
+    hasher.update([kind as u8]);
+    hasher.update(bytes);
+    P::ScalarField::from_le_bytes_mod_order(&hasher.finalize())
+}
+
+/// Split 32 bytes into two 16-byte little-endian field elements.
+fn to_halves<F: PrimeField>(bytes: &[u8; 32]) -> (F, F) {
+    (
+        F::from_le_bytes_mod_order(&bytes[..16]),
+        F::from_le_bytes_mod_order(&bytes[16..]),
+    )
+}
+
+/// Join two 16-byte little-endian field elements, inverse of `to_halves`.
+fn from_halves<F: PrimeField>(lo: &F, hi: &F) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[..16].copy_from_slice(&lo.into_bigint().to_bytes_le()[..16]);
+    bytes[16..].copy_from_slice(&hi.into_bigint().to_bytes_le()[..16]);
+    bytes
+}
+
+#[cfg(test)]
+mod tests_verkle {
+    use super::*;
+    use crate::backend::kzg::KZGCommitmentScheme;
+    use ark_bn254::Bn254;
+    use ark_std::{rand::RngCore, test_rng};
+
+    fn setup() -> VerkleTree<Bn254> {
+        let mut prng = test_rng();
+        let pcs = KZGCommitmentScheme::new(VERKLE_WIDTH - 1, &mut prng);
+        VerkleTree::new(VectorCommitment::new(pcs, VERKLE_WIDTH).unwrap()).unwrap()
+    }
+
+    fn key(prefix: &[u8], last: u8) -> VerkleKey {
+        let mut key = [0u8; 32];
+        key[..prefix.len()].copy_from_slice(prefix);
+        key[31] = last;
+        key
+    }
+
+    fn value(seed: u8) -> VerkleValue {
+        [seed; 32]
+    }
+
+    fn rebuild(
+        tree: &VerkleTree<Bn254>,
+        entries: &[(VerkleKey, VerkleValue)],
+    ) -> VerkleTree<Bn254> {
+        let mut fresh = VerkleTree::new(tree.vector_commitment().clone()).unwrap();
+        for (k, v) in entries {
+            fresh.insert(k, v).unwrap();
+        }
+        fresh
+    }
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut tree = setup();
+        let empty_root = tree.root();
+        let keys = [
+            key(&[1], 0),
+            key(&[1, 2, 3], 1),
+            key(&[1, 2, 3], 2),
+            key(&[7], 0),
+            key(&[255, 255], 9),
+        ];
+        for (i, k) in keys.iter().enumerate() {
+            tree.insert(k, &value(i as u8)).unwrap();
+        }
+        for (i, k) in keys.iter().enumerate() {
+            assert_eq!(tree.get(k), Some(value(i as u8)));
+        }
+        assert_eq!(tree.get(&key(&[1, 2], 0)), None);
+        assert_eq!(tree.get(&key(&[1, 2, 3], 3)), None);
+
+        // The commitments only depend on the entries.
+        let mut entries: Vec<_> = keys
+            .iter()
+            .enumerate()
+            .map(|(i, k)| (*k, value(i as u8)))
+            .collect();
+        entries.reverse();
+        assert_eq!(tree.root(), rebuild(&tree, &entries).root());
+
+        // Updates.
+        tree.insert(&keys[2], &value(42)).unwrap();
+        assert_eq!(tree.get(&keys[2]), Some(value(42)));
+        entries.retain(|(k, _)| *k != keys[2]);
+        entries.push((keys[2], value(42)));
+        assert_eq!(tree.root(), rebuild(&tree, &entries).root());
+
+        // Removals collapse the nodes left with a single leaf.
+        assert_eq!(tree.remove(&key(&[9], 0)).unwrap(), None);
+        assert_eq!(tree.remove(&key(&[1, 2, 3], 5)).unwrap(), None);
+        assert_eq!(tree.remove(&keys[1]).unwrap(), Some(value(1)));
+        assert_eq!(tree.get(&keys[1]), None);
+        entries.retain(|(k, _)| *k != keys[1]);
+        assert_eq!(tree, rebuild(&tree, &entries));
+
+        for (k, _) in entries.clone() {
+            tree.remove(&k).unwrap();
+            entries.retain(|(other, _)| *other != k);
+            assert_eq!(tree, rebuild(&tree, &entries));
+        }
+        assert_eq!(tree.root(), empty_root);
+    }
+
+    #[test]
+    fn test_random_operations() {
+        let mut prng = test_rng();
+        let mut tree = setup();
+        let mut entries = BTreeMap::new();
+        for _ in 0..20 {
+            let mut k = [0u8; 32];
+            prng.fill_bytes(&mut k);
+            // Share prefixes often.
+            k[0] %= 2;
+            k[1] %= 2;
+            let v = value(prng.next_u32() as u8);
+            if prng.next_u32() % 3 == 2 && !entries.is_empty() {
+                let existing = *entries.keys().next().unwrap();
+                assert_eq!(tree.remove(&existing).unwrap(), entries.remove(&existing));
+            } else {
+                tree.insert(&k, &v).unwrap();
+                entries.insert(k, v);
+            }
+        }
+        let entries: Vec<_> = entries.into_iter().collect();
+        assert_eq!(tree.root(), rebuild(&tree, &entries).root());
+    }
+
+    #[test]
+    fn test_multiproof() {
+        let mut tree = setup();
+        let keys = [
+            key(&[1], 0),
+            key(&[1, 2, 3], 1),
+            key(&[1, 2, 3], 2),
+            key(&[7], 0),
+        ];
+        for (i, k) in keys.iter().enumerate() {
+            tree.insert(k, &value(i as u8)).unwrap();
+        }
+        let vc = tree.vector_commitment().clone();
+        let root = tree.root();
+
+        // Present keys, a key absent below an empty child and a key absent
+        // below another leaf.
+        let queried = [keys[0], keys[2], key(&[9], 0), key(&[7, 1], 0)];
+        let values = [Some(value(0)), Some(value(2)), None, None];
+        let proof = tree.prove(&queried).unwrap();
+        VerkleTree::verify(&vc, &root, &queried, &values, &proof).unwrap();
+
+        // Wrong values or root.
+        let wrong = [Some(value(0)), Some(value(3)), None, None];
+        assert!(VerkleTree::verify(&vc, &root, &queried, &wrong, &proof).is_err());
+        let wrong = [Some(value(0)), Some(value(2)), Some(value(0)), None];
+        assert!(VerkleTree::verify(&vc, &root, &queried, &wrong, &proof).is_err());
+        let wrong = [None, Some(value(2)), None, None];
+        assert!(VerkleTree::verify(&vc, &root, &queried, &wrong, &proof).is_err());
+        let other_root = rebuild(&tree, &[(keys[0], value(0))]).root();
+        assert!(VerkleTree::verify(&vc, &other_root, &queried, &values, &proof).is_err());
+
+        // Tampered proofs.
+        let mut bad = proof.clone();
+        bad.nodes[1].values[0] += <Bn254 as Pairing>::ScalarField::ONE;
+        assert!(VerkleTree::verify(&vc, &root, &queried, &values, &bad).is_err());
+        let mut bad = proof.clone();
+        bad.proof.opening = bad.proof.quotient;
+        assert!(VerkleTree::verify(&vc, &root, &queried, &values, &bad).is_err());
+        let mut bad = proof.clone();
+        bad.nodes.pop();
+        assert!(VerkleTree::verify(&vc, &root, &queried, &values, &bad).is_err());
+
+        // A proof for a subset of keys does not prove the others.
+        let partial = tree.prove(&queried[..1]).unwrap();
+        assert!(VerkleTree::verify(&vc, &root, &queried, &values, &partial).is_err());
+        VerkleTree::verify(&vc, &root, &queried[..1], &values[..1], &partial).unwrap();
+    }
+}
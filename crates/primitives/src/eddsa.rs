@@ -0,0 +1,271 @@
+use ark_ff::{BigInteger, PrimeField, UniformRand};
+use ark_std::{
+    fmt::{self, Debug, Formatter},
+    rand::RngCore,
+};
+use sha2::{Digest, Sha512};
+
+use crate::{
+    edwards::{EdwardsPoint, FixedBaseTable, TwistedEdwardsConfig},
+    errs::{PrimitivesError, PrimitivesResult},
+    poseidon::Poseidon,
+};
+
+/// Window size of the fixed-base table of the generator.
+const WINDOW_BITS: usize = 4;
+
+/// Domain separator of the nonce derivation.
+const NONCE_DOMAIN: &[u8] = b"azk-eddsa-nonce";
+
+/// An EdDSA secret key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SecretKey<C: TwistedEdwardsConfig> {
+    /// The signing scalar.
+    pub scalar: C::ScalarField,
+    /// The key used to derive nonces deterministically.
+    pub nonce_key: [u8; 32],
+}
+
+impl<C: TwistedEdwardsConfig> Debug for SecretKey<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretKey(<redacted>)")
+    }
+}
+
+/// An EdDSA public key, the signing scalar times the generator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PublicKey<C: TwistedEdwardsConfig>(pub EdwardsPoint<C>);
+
+/// An EdDSA signature `(R, S)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Signature<C: TwistedEdwardsConfig> {
+    /// The nonce commitment `R = r * G`.
+    pub r: EdwardsPoint<C>,
+    /// The response `S = r + H(R, A, m) * s`.
+    pub s: C::ScalarField,
+}
+
+/// EdDSA over an embedded twisted Edwards curve, hashing with Poseidon over
+/// the base field, so that verification is cheap inside circuits.
+///
+/// Verification follows circomlib's EdDSA-Poseidon: with
+/// `h = Poseidon(R.x, R.y, A.x, A.y, m)` as circomlib hashes five inputs,
+/// it checks `S * G == R + (cofactor * h) * A`, after rejecting `R` and `A`
+/// outside the prime order subgroup. Over Baby Jubjub, whose generator is
+/// circomlib's `Base8`, circomlib signatures verify. The keys differ:
+/// circomlib derives the signing scalar and the nonces from a private key
+/// with Blake-512, while here nonces are SHA-512 digests of the nonce key
+/// and the message, reduced modulo the subgroup order, so that the 512-bit
+/// output leaves them unbiased.
+#[derive(Clone, Debug)]
+pub struct EdDSA<C: TwistedEdwardsConfig> {
+    /// Hash of the nonce commitment, the public key and the message.
+    challenge_hasher: Poseidon<C::BaseField>,
+    generator_table: FixedBaseTable<C>,
+}
+
+impl<C: TwistedEdwardsConfig> Default for EdDSA<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: TwistedEdwardsConfig> EdDSA<C> {
+    /// Create the hashers and the generator table.
+    pub fn new() -> Self {
+        Self {
+            challenge_hasher: Poseidon::new(6),
+            generator_table: FixedBaseTable::generator(WINDOW_BITS),
+        }
+    }
+
+    /// Sample a secret key.
+    pub fn keygen<R: RngCore>(&self, prng: &mut R) -> SecretKey<C> {
+        let mut nonce_key = [0u8; 32];
+        prng.fill_bytes(&mut nonce_key);
+        SecretKey {
+            scalar: C::ScalarField::rand(prng),
+            nonce_key,
+        }
+    }
+
+    /// Return the public key of `sk`.
+    pub fn public_key(&self, sk: &SecretKey<C>) -> PublicKey<C> {
+        PublicKey(self.generator_table.mul(&sk.scalar))
+    }
+
+    /// Sign `msg`, deriving the nonce deterministically from the nonce key
+    /// and the message.
+    pub fn sign(&self, sk: &SecretKey<C>, msg: &C::BaseField) -> Signature<C> {
+        let pk = self.public_key(sk);
+        let r = nonce::<C>(sk, msg);
+        let nonce_commitment = self.generator_table.mul(&r);
+        let challenge = self.challenge(&nonce_commitment, &pk, msg);
+        Signature {
+            r: nonce_commitment,
+            s: r + challenge * sk.scalar,
+        }
+    }
+
+    /// Verify the signature `sig` of `msg` under `pk`.
+    pub fn verify(
+        &self,
+        pk: &PublicKey<C>,
+        msg: &C::BaseField,
+        sig: &Signature<C>,
+    ) -> PrimitivesResult<()> {
+        // The addition law is only complete on the prime order subgroup.
+        if !pk.0.is_in_prime_subgroup() || !sig.r.is_in_prime_subgroup() {
+            return Err(PrimitivesError::SignatureError);
+        }
+        let challenge = self.challenge(&sig.r, pk, msg);
+        let lhs = self.generator_table.mul(&sig.s);
+        let rhs = sig.r.add(&pk.0.mul(&challenge));
+        if lhs == rhs {
+            Ok(())
+        } else {
+            Err(PrimitivesError::SignatureError)
+        }
+    }
+
+    fn challenge(
+        &self,
+        nonce_commitment: &EdwardsPoint<C>,
+        pk: &PublicKey<C>,
+        msg: &C::BaseField,
+    ) -> C::ScalarField {
+        let hash = self.challenge_hasher.hash_circomlib(&[
+            nonce_commitment.x,
+            nonce_commitment.y,
+            pk.0.x,
+            pk.0.y,
+            *msg,
+        ]);
+        to_scalar::<C>(&hash) * C::ScalarField::from(C::COFACTOR)
+    }
+}
+
+/// Derive the nonce as `SHA-512(domain || nonce_key || m)` reduced modulo
+/// the subgroup order.
+fn nonce<C: TwistedEdwardsConfig>(sk: &SecretKey<C>, msg: &C::BaseField) -> C::ScalarField {
+    let mut hasher = Sha512::new();
+    hasher.update(NONCE_DOMAIN);
+    hasher.update(sk.nonce_key);
+    hasher.update(msg.into_bigint().to_bytes_le());
+    C::ScalarField::from_le_bytes_mod_order(&hasher.finalize())
+}
+
+/// Reduce a base field element modulo the subgroup order.
+fn to_scalar<C: TwistedEdwardsConfig>(elem: &C::BaseField) -> C::ScalarField {
+    C::ScalarField::from_le_bytes_mod_order(&elem.into_bigint().to_bytes_le())
+}
+
+macro_rules! _test_eddsa {
+    ($config: ty) => {
+        type Base = <$config as TwistedEdwardsConfig>::BaseField;
+
+        #[test]
+        fn test_sign_verify() {
+            let mut prng = test_rng();
+            let eddsa = EdDSA::<$config>::new();
+            let sk = eddsa.keygen(&mut prng);
+            let pk = eddsa.public_key(&sk);
+            assert!(pk.0.is_in_prime_subgroup());
+
+            let msg = Base::rand(&mut prng);
+            let sig = eddsa.sign(&sk, &msg);
+            eddsa.verify(&pk, &msg, &sig).unwrap();
+            // Signing is deterministic, with nonces depending on the message.
+            assert_eq!(sig, eddsa.sign(&sk, &msg));
+            assert_ne!(sig.r, eddsa.sign(&sk, &(msg + Base::from(1u64))).r);
+
+            // Wrong message, key or signature.
+            assert!(eddsa.verify(&pk, &(msg + Base::from(1u64)), &sig).is_err());
+            let other_pk = eddsa.public_key(&eddsa.keygen(&mut prng));
+            assert!(eddsa.verify(&other_pk, &msg, &sig).is_err());
+            let mut bad_sig = sig;
+            bad_sig.s += <$config as TwistedEdwardsConfig>::ScalarField::from(1u64);
+            assert!(eddsa.verify(&pk, &msg, &bad_sig).is_err());
+            let mut bad_sig = sig;
+            bad_sig.r = bad_sig.r.double();
+            assert!(eddsa.verify(&pk, &msg, &bad_sig).is_err());
+            let mut bad_sig = sig;
+            bad_sig.r.x += Base::from(1u64);
+            assert!(eddsa.verify(&pk, &msg, &bad_sig).is_err());
+        }
+
+        #[test]
+        fn test_points_outside_subgroup() {
+            let mut prng = test_rng();
+            let eddsa = EdDSA::<$config>::new();
+            let sk = eddsa.keygen(&mut prng);
+            let pk = eddsa.public_key(&sk);
+            let msg = Base::rand(&mut prng);
+            let sig = eddsa.sign(&sk, &msg);
+
+            // Adding the 2-torsion point (0, -1) maps (x, y) to (-x, -y),
+            // which stays on the curve but leaves the subgroup.
+            let mut bad_sig = sig;
+            bad_sig.r = EdwardsPoint::new_unchecked(-sig.r.x, -sig.r.y);
+            assert!(bad_sig.r.is_on_curve());
+            assert!(eddsa.verify(&pk, &msg, &bad_sig).is_err());
+            let bad_pk = PublicKey(EdwardsPoint::new_unchecked(-pk.0.x, -pk.0.y));
+            assert!(bad_pk.0.is_on_curve());
+            assert!(eddsa.verify(&bad_pk, &msg, &sig).is_err());
+        }
+    };
+}
+
+#[cfg(test)]
+mod test_eddsa_baby_jubjub {
+    use super::{EdDSA, PublicKey, Signature};
+    use crate::edwards::{curves::BabyJubjubConfig, EdwardsPoint, TwistedEdwardsConfig};
+    use ark_ff::{MontFp, PrimeField, UniformRand};
+    use ark_std::test_rng;
+
+    _test_eddsa!(BabyJubjubConfig);
+
+    #[test]
+    fn test_circomlib_signature() {
+        // The EdDSA-Poseidon signature of the circomlibjs tests.
+        let msg = Base::from_le_bytes_mod_order(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0, 0]);
+        let pk = PublicKey::<BabyJubjubConfig>(
+            EdwardsPoint::new(
+                MontFp!(
+                    "13277427435165878497778222415993513565335242147425444199013288855685581939618"
+                ),
+                MontFp!(
+                    "13622229784656158136036771217484571176836296686641868549125388198837476602820"
+                ),
+            )
+            .unwrap(),
+        );
+        let sig = Signature {
+            r: EdwardsPoint::new(
+                MontFp!(
+                    "11384336176656855268977457483345535180380036354188103142384839473266348197733"
+                ),
+                MontFp!(
+                    "15383486972088797283337779941324724402501462225528836549661220478783371668959"
+                ),
+            )
+            .unwrap(),
+            s: MontFp!(
+                "1672775540645840396591609181675628451599263765380031905495115170613215233181"
+            ),
+        };
+        let eddsa = EdDSA::new();
+        eddsa.verify(&pk, &msg, &sig).unwrap();
+        assert!(eddsa.verify(&pk, &(msg + Base::from(1u64)), &sig).is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_eddsa_jubjub {
+    use super::{EdDSA, PublicKey};
+    use crate::edwards::{curves::JubjubConfig, EdwardsPoint, TwistedEdwardsConfig};
+    use ark_ff::UniformRand;
+    use ark_std::test_rng;
+
+    _test_eddsa!(JubjubConfig);
+}
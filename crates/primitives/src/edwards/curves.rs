@@ -0,0 +1,86 @@
+use ark_ec::{twisted_edwards::TECurveConfig, CurveConfig};
+use ark_ff::{
+    fields::{Fp256, MontBackend, MontConfig},
+    MontFp,
+};
+
+use super::{EdwardsPoint, TwistedEdwardsConfig};
+
+#[derive(MontConfig)]
+#[modulus = "2736030358979909402780800718157159386076813972158567259200215660948447373041"]
+#[generator = "31"]
+pub struct BabyJubjubScalarConfig;
+
+/// The scalar field of the prime order subgroup of Baby Jubjub.
+pub type BabyJubjubScalar = Fp256<MontBackend<BabyJubjubScalarConfig, 4>>;
+
+#[derive(MontConfig)]
+#[modulus = "6554484396890773809930967563523245729705921265872317281365359162392183254199"]
+#[generator = "6"]
+pub struct JubjubScalarConfig;
+
+/// The scalar field of the prime order subgroup of Jubjub.
+pub type JubjubScalar = Fp256<MontBackend<JubjubScalarConfig, 4>>;
+
+/// The scalar field of the prime order subgroup of Bandersnatch.
+pub type BandersnatchScalar = ark_ed_on_bls12_381_bandersnatch::Fr;
+
+/// Baby Jubjub, embedded in BN254, in the form used by circomlib
+/// (`a = 168700`, `d = 168696`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BabyJubjubConfig;
+
+impl TwistedEdwardsConfig for BabyJubjubConfig {
+    type BaseField = ark_bn254::Fr;
+    type ScalarField = BabyJubjubScalar;
+
+    const COEFF_A: Self::BaseField = MontFp!("168700");
+    const COEFF_D: Self::BaseField = MontFp!("168696");
+    const COFACTOR: u64 = 8;
+
+    /// The circomlib `Base8` point, eight times the curve generator.
+    const GENERATOR: EdwardsPoint<Self> = EdwardsPoint::new_unchecked(
+        MontFp!("5299619240641551281634865583518297030282874472190772894086521144482721001553"),
+        MontFp!("16950150798460657717958625567821834550301663161624707787222815936182638968203"),
+    );
+}
+
+/// Jubjub, embedded in BLS12-381 (`a = -1`, `d = -10240 / 10241`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct JubjubConfig;
+
+impl TwistedEdwardsConfig for JubjubConfig {
+    type BaseField = ark_bls12_381::Fr;
+    type ScalarField = JubjubScalar;
+
+    const COEFF_A: Self::BaseField = MontFp!("-1");
+    const COEFF_D: Self::BaseField =
+        MontFp!("19257038036680949359750312669786877991949435402254120286184196891950884077233");
+    const COFACTOR: u64 = 8;
+
+    const GENERATOR: EdwardsPoint<Self> = EdwardsPoint::new_unchecked(
+        MontFp!("8076246640662884909881801758704306714034609987455869804520522091855516602923"),
+        MontFp!("13262374693698910701929044844600465831413122818447359594527400194675274060458"),
+    );
+}
+
+/// Bandersnatch, embedded in BLS12-381 (`a = -5`), with the parameters of
+/// arkworks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BandersnatchConfig;
+
+type ArkBandersnatchConfig = ark_ed_on_bls12_381_bandersnatch::BandersnatchConfig;
+
+impl TwistedEdwardsConfig for BandersnatchConfig {
+    type BaseField = ark_ed_on_bls12_381_bandersnatch::Fq;
+    type ScalarField = BandersnatchScalar;
+
+    const COEFF_A: Self::BaseField = <ArkBandersnatchConfig as TECurveConfig>::COEFF_A;
+    const COEFF_D: Self::BaseField = <ArkBandersnatchConfig as TECurveConfig>::COEFF_D;
+    const COFACTOR: u64 = <ArkBandersnatchConfig as CurveConfig>::COFACTOR[0];
+
+    const GENERATOR: EdwardsPoint<Self> = EdwardsPoint::new_unchecked(
+        <ArkBandersnatchConfig as TECurveConfig>::GENERATOR.x,
+        <ArkBandersnatchConfig as TECurveConfig>::GENERATOR.y,
+    );
+}
@@ -0,0 +1,413 @@
+pub mod curves;
+
+use ark_ff::{AdditiveGroup, BigInteger, Field, PrimeField};
+use ark_std::fmt::Debug;
+
+/// Parameters of a twisted Edwards curve `a * x^2 + y^2 = 1 + d * x^2 * y^2`
+/// embedded in a pairing-friendly curve, i.e. whose base field is the scalar
+/// field of the outer curve, so that its arithmetic is native in circuits.
+pub trait TwistedEdwardsConfig:
+    'static + Copy + Clone + Debug + PartialEq + Eq + Send + Sync
+{
+    /// The field of the coordinates, the scalar field of the outer curve.
+    type BaseField: PrimeField;
+
+    /// The scalar field of the prime order subgroup.
+    type ScalarField: PrimeField;
+
+    /// Coefficient `a` of the curve equation.
+    const COEFF_A: Self::BaseField;
+
+    /// Coefficient `d` of the curve equation.
+    const COEFF_D: Self::BaseField;
+
+    /// The cofactor of the prime order subgroup.
+    const COFACTOR: u64;
+
+    /// A generator of the prime order subgroup.
+    const GENERATOR: EdwardsPoint<Self>;
+}
+
+/// A point of a twisted Edwards curve in affine coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EdwardsPoint<C: TwistedEdwardsConfig> {
+    /// The x coordinate.
+    pub(crate) x: C::BaseField,
+    /// The y coordinate.
+    pub(crate) y: C::BaseField,
+}
+
+impl<C: TwistedEdwardsConfig> Default for EdwardsPoint<C> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<C: TwistedEdwardsConfig> EdwardsPoint<C> {
+    /// Build a point from its coordinates, without checking it is on the curve.
+    pub(crate) const fn new_unchecked(x: C::BaseField, y: C::BaseField) -> Self {
+        Self { x, y }
+    }
+
+    /// Build a point from its coordinates, returning `None` if it is not on the
+    /// curve or not in the prime order subgroup, where the addition law is
+    /// complete.
+    pub fn new(x: C::BaseField, y: C::BaseField) -> Option<Self> {
+        let point = Self { x, y };
+        point.is_in_prime_subgroup().then_some(point)
+    }
+
+    /// Return the x coordinate.
+    pub fn x(&self) -> C::BaseField {
+        self.x
+    }
+
+    /// Return the y coordinate.
+    pub fn y(&self) -> C::BaseField {
+        self.y
+    }
+
+    /// Return the neutral element `(0, 1)`.
+    pub fn identity() -> Self {
+        Self {
+            x: C::BaseField::ZERO,
+            y: C::BaseField::ONE,
+        }
+    }
+
+    /// Return the generator of the prime order subgroup.
+    pub fn generator() -> Self {
+        C::GENERATOR
+    }
+
+    /// Test if the point is the neutral element.
+    pub fn is_identity(&self) -> bool {
+        self.x == C::BaseField::ZERO && self.y == C::BaseField::ONE
+    }
+
+    /// Test if the point satisfies the curve equation.
+    pub fn is_on_curve(&self) -> bool {
+        let x2 = self.x.square();
+        let y2 = self.y.square();
+        C::COEFF_A * x2 + y2 == C::BaseField::ONE + C::COEFF_D * x2 * y2
+    }
+
+    /// Test if the point is on the curve and in the prime order subgroup.
+    /// Points outside the subgroup may reach an exceptional case of the
+    /// addition law, which also rejects them.
+    pub fn is_in_prime_subgroup(&self) -> bool {
+        self.is_on_curve()
+            && self
+                .checked_mul_bits(&C::ScalarField::MODULUS.to_bits_le())
+                .is_some_and(|point| point.is_identity())
+    }
+
+    /// Add two points with the unified addition law
+    /// `x3 = (x1 * y2 + y1 * x2) / (1 + d * x1 * x2 * y1 * y2)`,
+    /// `y3 = (y1 * y2 - a * x1 * x2) / (1 - d * x1 * x2 * y1 * y2)`,
+    /// which also covers doubling and the neutral element. The law is
+    /// complete on the prime order subgroup, where points are built. On
+    /// Bandersnatch, whose `a` is not a square, the sum of other points may be
+    /// one of the points at infinity, which has no affine form.
+    pub fn add(&self, other: &Self) -> Self {
+        self.checked_add(other)
+            .expect("the addition law is complete on the prime order subgroup")
+    }
+
+    /// Add two points, returning `None` in the exceptional cases of the
+    /// addition law.
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        let x1x2 = self.x * other.x;
+        let y1y2 = self.y * other.y;
+        let dxy = C::COEFF_D * x1x2 * y1y2;
+        let x = (self.x * other.y + self.y * other.x) * (C::BaseField::ONE + dxy).inverse()?;
+        let y = (y1y2 - C::COEFF_A * x1x2) * (C::BaseField::ONE - dxy).inverse()?;
+        Some(Self { x, y })
+    }
+
+    /// Subtract another point.
+    pub fn sub(&self, other: &Self) -> Self {
+        self.add(&other.neg())
+    }
+
+    /// Double the point.
+    pub fn double(&self) -> Self {
+        self.add(self)
+    }
+
+    /// Negate the point.
+    pub fn neg(&self) -> Self {
+        Self {
+            x: -self.x,
+            y: self.y,
+        }
+    }
+
+    /// Multiply the point by a scalar given as little-endian bits, with
+    /// double-and-add from the most significant bit.
+    pub fn mul_bits(&self, bits: &[bool]) -> Self {
+        self.checked_mul_bits(bits)
+            .expect("the addition law is complete on the prime order subgroup")
+    }
+
+    /// Multiply the point by a scalar given as little-endian bits, returning
+    /// `None` if an addition reaches an exceptional case.
+    fn checked_mul_bits(&self, bits: &[bool]) -> Option<Self> {
+        let mut result = Self::identity();
+        for bit in bits.iter().rev() {
+            result = result.checked_add(&result)?;
+            if *bit {
+                result = result.checked_add(self)?;
+            }
+        }
+        Some(result)
+    }
+
+    /// Multiply the point by an element of the subgroup scalar field.
+    pub fn mul(&self, scalar: &C::ScalarField) -> Self {
+        self.mul_bits(&scalar.into_bigint().to_bits_le())
+    }
+
+    /// Multiply the point by the cofactor.
+    pub fn mul_by_cofactor(&self) -> Self {
+        self.mul_bits(&<C::ScalarField as PrimeField>::BigInt::from(C::COFACTOR).to_bits_le())
+    }
+}
+
+/// Precomputed multiples of a fixed base for windowed scalar multiplication:
+/// `table[i][j] = j * 2^(window_bits * i) * base`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FixedBaseTable<C: TwistedEdwardsConfig> {
+    window_bits: usize,
+    table: Vec<Vec<EdwardsPoint<C>>>,
+}
+
+impl<C: TwistedEdwardsConfig> FixedBaseTable<C> {
+    /// Precompute the table of `base` for scalars of up to `num_bits` bits.
+    pub fn new(base: &EdwardsPoint<C>, window_bits: usize, num_bits: usize) -> Self {
+        assert!(window_bits > 0);
+        let num_windows = num_bits.div_ceil(window_bits);
+        let mut window_base = *base;
+        let mut table = Vec::with_capacity(num_windows);
+        for _ in 0..num_windows {
+            let mut multiples = vec![EdwardsPoint::identity()];
+            for j in 1..1 << window_bits {
+                multiples.push(multiples[j - 1].add(&window_base));
+            }
+            window_base = multiples[(1 << window_bits) - 1].add(&window_base);
+            table.push(multiples);
+        }
+        Self { window_bits, table }
+    }
+
+    /// Precompute the table of the subgroup generator for full-size scalars.
+    pub fn generator(window_bits: usize) -> Self {
+        Self::new(
+            &EdwardsPoint::generator(),
+            window_bits,
+            C::ScalarField::MODULUS_BIT_SIZE as usize,
+        )
+    }
+
+    /// Return the window size in bits.
+    pub fn window_bits(&self) -> usize {
+        self.window_bits
+    }
+
+    /// Return the precomputed multiples, one vector per window.
+    pub fn table(&self) -> &[Vec<EdwardsPoint<C>>] {
+        &self.table
+    }
+
+    /// Multiply the base by a scalar given as little-endian bits, adding one
+    /// table entry per window.
+    pub fn mul_bits(&self, bits: &[bool]) -> EdwardsPoint<C> {
+        assert!(bits.len() <= self.table.len() * self.window_bits);
+        bits.chunks(self.window_bits).zip(self.table.iter()).fold(
+            EdwardsPoint::identity(),
+            |acc, (window, multiples)| {
+                let digit = window
+                    .iter()
+                    .rev()
+                    .fold(0, |digit, bit| (digit << 1) | *bit as usize);
+                acc.add(&multiples[digit])
+            },
+        )
+    }
+
+    /// Multiply the base by an element of the subgroup scalar field.
+    pub fn mul(&self, scalar: &C::ScalarField) -> EdwardsPoint<C> {
+        let mut bits = scalar.into_bigint().to_bits_le();
+        bits.truncate(C::ScalarField::MODULUS_BIT_SIZE as usize);
+        self.mul_bits(&bits)
+    }
+}
+
+macro_rules! _test_edwards_curve {
+    ($config: ty) => {
+        type Point = EdwardsPoint<$config>;
+        type Scalar = <$config as TwistedEdwardsConfig>::ScalarField;
+        type Base = <$config as TwistedEdwardsConfig>::BaseField;
+
+        #[test]
+        fn test_generator() {
+            let g = Point::generator();
+            assert!(g.is_on_curve());
+            assert!(g.is_in_prime_subgroup());
+            assert!(!g.is_identity());
+            assert!(Point::identity().is_in_prime_subgroup());
+            assert!(Point::new(g.x, g.y + g.y).is_none());
+
+            // Points of small order or outside the subgroup are rejected.
+            let torsion = Point::new_unchecked(Base::from(0u64), -Base::from(1u64));
+            assert!(torsion.is_on_curve());
+            assert!(Point::new(torsion.x, torsion.y).is_none());
+            let p = g.add(&torsion);
+            assert!(p.is_on_curve());
+            assert!(Point::new(p.x, p.y).is_none());
+        }
+
+        #[test]
+        fn test_group_law() {
+            let mut prng = test_rng();
+            let g = Point::generator();
+            let p = g.mul(&Scalar::rand(&mut prng));
+            let q = g.mul(&Scalar::rand(&mut prng));
+            let r = g.mul(&Scalar::rand(&mut prng));
+
+            assert_eq!(p.add(&q), q.add(&p));
+            assert_eq!(p.add(&q).add(&r), p.add(&q.add(&r)));
+            assert_eq!(p.add(&Point::identity()), p);
+            assert!(p.sub(&p).is_identity());
+            assert_eq!(p.double(), p.add(&p));
+            assert!(p.add(&q).is_on_curve());
+        }
+
+        #[test]
+        fn test_scalar_mul() {
+            let mut prng = test_rng();
+            let g = Point::generator();
+            let a = Scalar::rand(&mut prng);
+            let b = Scalar::rand(&mut prng);
+
+            assert_eq!(g.mul(&a).add(&g.mul(&b)), g.mul(&(a + b)));
+            assert_eq!(g.mul(&a).mul(&b), g.mul(&(a * b)));
+            assert_eq!(g.mul(&Scalar::from(3u64)), g.add(&g).add(&g));
+            assert!(g.mul(&Scalar::from(0u64)).is_identity());
+            assert_eq!(
+                g.mul_by_cofactor(),
+                g.mul(&Scalar::from(<$config>::COFACTOR))
+            );
+        }
+
+        #[test]
+        fn test_fixed_base() {
+            let mut prng = test_rng();
+            let g = Point::generator();
+            for window_bits in [1, 3, 4] {
+                let table = FixedBaseTable::<$config>::generator(window_bits);
+                for _ in 0..4 {
+                    let a = Scalar::rand(&mut prng);
+                    assert_eq!(table.mul(&a), g.mul(&a));
+                }
+            }
+            let p = g.mul(&Scalar::rand(&mut prng));
+            let table = FixedBaseTable::new(&p, 2, 8);
+            let bits = [true, false, true, true, false, false, true, true];
+            assert_eq!(table.mul_bits(&bits), p.mul_bits(&bits));
+        }
+    };
+}
+
+#[cfg(test)]
+mod test_baby_jubjub {
+    use super::{curves::BabyJubjubConfig, EdwardsPoint, FixedBaseTable, TwistedEdwardsConfig};
+    use ark_ff::{MontFp, UniformRand};
+    use ark_std::test_rng;
+
+    _test_edwards_curve!(BabyJubjubConfig);
+
+    #[test]
+    fn test_circomlib_vectors() {
+        let p1 = Point::new(
+            MontFp!(
+                "17777552123799933955779906779655732241715742912184938656739573121738514868268"
+            ),
+            MontFp!("2626589144620713026669568689430873010625803728049924121243784502389097019475"),
+        )
+        .unwrap();
+        let p2 = Point::new(
+            MontFp!(
+                "16540640123574156134436876038791482806971768689494387082833631921987005038935"
+            ),
+            MontFp!(
+                "20819045374670962167435360035096875258406992893633759881276124905556507972311"
+            ),
+        )
+        .unwrap();
+        assert_eq!(
+            p1.double(),
+            Point::new_unchecked(
+                MontFp!(
+                    "6890855772600357754907169075114257697580319025794532037257385534741338397365"
+                ),
+                MontFp!(
+                    "4338620300185947561074059802482547481416142213883829469920100239455078257889"
+                ),
+            )
+        );
+        assert_eq!(
+            p1.add(&p2),
+            Point::new_unchecked(
+                MontFp!(
+                    "7916061937171219682591368294088513039687205273691143098332585753343424131937"
+                ),
+                MontFp!(
+                    "14035240266687799601661095864649209771790948434046947201833777492504781204499"
+                ),
+            )
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_jubjub {
+    use super::{curves::JubjubConfig, EdwardsPoint, FixedBaseTable, TwistedEdwardsConfig};
+    use ark_ff::UniformRand;
+    use ark_std::test_rng;
+
+    _test_edwards_curve!(JubjubConfig);
+}
+
+#[cfg(test)]
+mod test_bandersnatch {
+    use super::{curves::BandersnatchConfig, EdwardsPoint, FixedBaseTable, TwistedEdwardsConfig};
+    use ark_ed_on_bls12_381_bandersnatch::EdwardsAffine;
+    use ark_ff::{Field, UniformRand};
+    use ark_std::test_rng;
+
+    _test_edwards_curve!(BandersnatchConfig);
+
+    #[test]
+    fn test_points_outside_subgroup() {
+        // Multiplying points outside the subgroup may reach a point at
+        // infinity, which rejects them instead of panicking.
+        let mut rejected = 0;
+        for y in 2..32u64 {
+            let y = Base::from(y);
+            let x2 = (Base::from(1u64) - y.square())
+                / (BandersnatchConfig::COEFF_A - BandersnatchConfig::COEFF_D * y.square());
+            if let Some(x) = x2.sqrt() {
+                let point = Point::new_unchecked(x, y);
+                assert!(point.is_on_curve());
+                let in_subgroup =
+                    EdwardsAffine::new_unchecked(x, y).is_in_correct_subgroup_assuming_on_curve();
+                assert_eq!(Point::new(x, y).is_some(), in_subgroup);
+                if !in_subgroup {
+                    rejected += 1;
+                }
+            }
+        }
+        assert!(rejected > 0);
+    }
+}
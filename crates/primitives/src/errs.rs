@@ -0,0 +1,15 @@
+use thiserror::Error;
+
+/// Result type
+pub type PrimitivesResult<T> = Result<T, PrimitivesError>;
+#[derive(Debug, Error)]
+pub enum PrimitivesError {
+    #[error("Merkle tree arity error")]
+    MerkleArityError,
+    #[error("Merkle tree index out of range")]
+    MerkleIndexError,
+    #[error("Merkle path verification error")]
+    MerklePathError,
+    #[error("Signature verification error")]
+    SignatureError,
+}
@@ -0,0 +1,5 @@
+pub mod eddsa;
+pub mod edwards;
+pub mod errs;
+pub mod merkle;
+pub mod poseidon;
@@ -0,0 +1,288 @@
+pub mod sparse;
+
+use ark_ff::PrimeField;
+
+use crate::{
+    errs::{PrimitivesError, PrimitivesResult},
+    poseidon::Poseidon,
+};
+
+/// Check the arity and return the Poseidon instance hashing `arity` children.
+fn node_hasher<F: PrimeField>(arity: usize) -> PrimitivesResult<Poseidon<F>> {
+    if !(2..=16).contains(&arity) {
+        return Err(PrimitivesError::MerkleArityError);
+    }
+    Ok(Poseidon::new(arity + 1))
+}
+
+/// An authentication path from a leaf to the root, bottom-up.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerklePath<F: PrimeField> {
+    /// Index of the leaf.
+    pub index: u64,
+    /// For every level, the `arity - 1` siblings of the node on the path,
+    /// in their left-to-right order.
+    pub siblings: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> MerklePath<F> {
+    /// Return the arity of the tree this path belongs to.
+    pub fn arity(&self) -> usize {
+        self.siblings.first().map_or(0, |s| s.len() + 1)
+    }
+
+    /// Return the position of the path node among its siblings at every level.
+    pub fn positions(&self) -> Vec<usize> {
+        let arity = self.arity() as u64;
+        let mut index = self.index;
+        self.siblings
+            .iter()
+            .map(|_| {
+                let position = (index % arity) as usize;
+                index /= arity;
+                position
+            })
+            .collect()
+    }
+
+    /// Compute the root from `leaf` and the siblings. The path is rejected if
+    /// a level does not have `arity - 1` siblings or if the index does not
+    /// fit in the depth of the path, so that every leaf has a single index.
+    pub fn compute_root(&self, hasher: &Poseidon<F>, leaf: &F) -> PrimitivesResult<F> {
+        let arity = self.arity();
+        if self.siblings.iter().any(|s| s.len() + 1 != arity) {
+            return Err(PrimitivesError::MerklePathError);
+        }
+        match (arity as u128).checked_pow(self.siblings.len() as u32) {
+            Some(num_leaves) if (self.index as u128) < num_leaves => {}
+            Some(_) => return Err(PrimitivesError::MerkleIndexError),
+            None => {}
+        }
+
+        let mut node = *leaf;
+        for (siblings, position) in self.siblings.iter().zip(self.positions()) {
+            let mut children = siblings.clone();
+            children.insert(position, node);
+            node = hasher.hash(&children);
+        }
+        Ok(node)
+    }
+
+    /// Verify that `leaf` is at `self.index` in the tree of root `root` and
+    /// `depth` levels above the leaves. Without the depth, a path stopping at
+    /// an internal node would prove that node as a leaf.
+    pub fn verify(&self, depth: usize, root: &F, leaf: &F) -> PrimitivesResult<()> {
+        if self.siblings.len() != depth {
+            return Err(PrimitivesError::MerklePathError);
+        }
+        let hasher = node_hasher(self.arity())?;
+        if self.compute_root(&hasher, leaf)? == *root {
+            Ok(())
+        } else {
+            Err(PrimitivesError::MerklePathError)
+        }
+    }
+}
+
+/// A dense Poseidon Merkle tree of configurable arity.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleTree<F: PrimeField> {
+    arity: usize,
+    hasher: Poseidon<F>,
+    /// All the nodes, level by level from the leaves to the root.
+    levels: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> MerkleTree<F> {
+    /// Build a tree over `leaves`, padded with zeroes to the next power of `arity`.
+    pub fn new(arity: usize, leaves: &[F]) -> PrimitivesResult<Self> {
+        let hasher = node_hasher(arity)?;
+        let mut num_leaves = 1;
+        while num_leaves < leaves.len().max(arity) {
+            num_leaves *= arity;
+        }
+
+        let mut bottom = leaves.to_vec();
+        bottom.resize(num_leaves, F::ZERO);
+        let mut levels = vec![bottom];
+        while levels.last().unwrap().len() > 1 {
+            let level = levels
+                .last()
+                .unwrap()
+                .chunks(arity)
+                .map(|children| hasher.hash(children))
+                .collect();
+            levels.push(level);
+        }
+
+        Ok(Self {
+            arity,
+            hasher,
+            levels,
+        })
+    }
+
+    /// Return the arity of the tree.
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
+    /// Return the number of levels above the leaves.
+    pub fn depth(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    /// Return the root of the tree.
+    pub fn root(&self) -> F {
+        self.levels.last().unwrap()[0]
+    }
+
+    /// Return the leaves, including the padding.
+    pub fn leaves(&self) -> &[F] {
+        &self.levels[0]
+    }
+
+    /// Return the authentication path of the leaf at `index`.
+    pub fn path(&self, index: u64) -> PrimitivesResult<MerklePath<F>> {
+        if index >= self.levels[0].len() as u64 {
+            return Err(PrimitivesError::MerkleIndexError);
+        }
+        let mut pos = index as usize;
+        let siblings = self.levels[..self.depth()]
+            .iter()
+            .map(|level| {
+                let first = pos - pos % self.arity;
+                let siblings = (first..first + self.arity)
+                    .filter(|i| *i != pos)
+                    .map(|i| level[i])
+                    .collect();
+                pos /= self.arity;
+                siblings
+            })
+            .collect();
+        Ok(MerklePath { index, siblings })
+    }
+
+    /// Set the leaf at `index` to `value`, updating the nodes above it.
+    pub fn update(&mut self, index: u64, value: F) -> PrimitivesResult<()> {
+        if index >= self.levels[0].len() as u64 {
+            return Err(PrimitivesError::MerkleIndexError);
+        }
+        let mut pos = index as usize;
+        self.levels[0][pos] = value;
+        for level in 1..self.levels.len() {
+            let first = pos - pos % self.arity;
+            let node = self
+                .hasher
+                .hash(&self.levels[level - 1][first..first + self.arity]);
+            pos /= self.arity;
+            self.levels[level][pos] = node;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_merkle {
+    use super::*;
+    use ark_bn254::Fr;
+
+    fn leaves(n: u64) -> Vec<Fr> {
+        (0..n).map(|i| Fr::from(i * i + 7)).collect()
+    }
+
+    #[test]
+    fn test_paths() {
+        for arity in [2, 3, 4, 8] {
+            let leaves = leaves(20);
+            let tree = MerkleTree::new(arity, &leaves).unwrap();
+            assert_eq!(tree.leaves().len(), arity.pow(tree.depth() as u32));
+            for (i, leaf) in tree.leaves().iter().enumerate() {
+                let path = tree.path(i as u64).unwrap();
+                assert_eq!(path.arity(), arity);
+                assert_eq!(path.siblings.len(), tree.depth());
+                path.verify(tree.depth(), &tree.root(), leaf).unwrap();
+                assert!(path
+                    .verify(tree.depth(), &tree.root(), &(*leaf + Fr::from(1u64)))
+                    .is_err());
+            }
+
+            // A path for another index does not verify the same leaf.
+            let mut path = tree.path(3).unwrap();
+            path.index = 4;
+            assert!(path.verify(tree.depth(), &tree.root(), &leaves[3]).is_err());
+            assert!(tree.path(tree.leaves().len() as u64).is_err());
+        }
+    }
+
+    #[test]
+    fn test_malleable_index() {
+        let tree = MerkleTree::new(2, &leaves(8)).unwrap();
+        let mut path = tree.path(3).unwrap();
+        // Extra high digits would select the same leaf.
+        path.index = 3 + 8;
+        assert!(matches!(
+            path.verify(tree.depth(), &tree.root(), &tree.leaves()[3]),
+            Err(PrimitivesError::MerkleIndexError)
+        ));
+    }
+
+    #[test]
+    fn test_malformed_path() {
+        let tree = MerkleTree::new(4, &leaves(16)).unwrap();
+        let leaf = tree.leaves()[5];
+        let mut path = tree.path(5).unwrap();
+        path.siblings[1].pop();
+        assert!(matches!(
+            path.verify(tree.depth(), &tree.root(), &leaf),
+            Err(PrimitivesError::MerklePathError)
+        ));
+        path.siblings[1].clear();
+        assert!(path.verify(tree.depth(), &tree.root(), &leaf).is_err());
+        let path = MerklePath::<Fr> {
+            index: 0,
+            siblings: vec![],
+        };
+        assert!(path.verify(tree.depth(), &tree.root(), &leaf).is_err());
+    }
+
+    #[test]
+    fn test_depth() {
+        let tree = MerkleTree::new(2, &leaves(8)).unwrap();
+        let mut path = tree.path(5).unwrap();
+        assert!(path
+            .verify(tree.depth() + 1, &tree.root(), &tree.leaves()[5])
+            .is_err());
+        // The path of an internal node does not prove it as a leaf.
+        let node = tree.levels[1][2];
+        path.siblings.remove(0);
+        path.index = 2;
+        path.verify(tree.depth() - 1, &tree.root(), &node).unwrap();
+        assert!(matches!(
+            path.verify(tree.depth(), &tree.root(), &node),
+            Err(PrimitivesError::MerklePathError)
+        ));
+    }
+
+    #[test]
+    fn test_update() {
+        let mut leaves = leaves(9);
+        let mut tree = MerkleTree::new(3, &leaves).unwrap();
+        leaves[5] = Fr::from(1234u64);
+        tree.update(5, leaves[5]).unwrap();
+        assert_eq!(tree, MerkleTree::new(3, &leaves).unwrap());
+        tree.path(5)
+            .unwrap()
+            .verify(tree.depth(), &tree.root(), &leaves[5])
+            .unwrap();
+        assert!(tree.update(9, leaves[5]).is_err());
+    }
+
+    #[test]
+    fn test_arity() {
+        assert!(MerkleTree::<Fr>::new(1, &leaves(4)).is_err());
+        assert!(MerkleTree::<Fr>::new(17, &leaves(4)).is_err());
+        let tree = MerkleTree::<Fr>::new(4, &leaves(1)).unwrap();
+        assert_eq!(tree.depth(), 1);
+    }
+}
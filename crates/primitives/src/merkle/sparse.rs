@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+
+use ark_ff::PrimeField;
+
+use super::{node_hasher, MerklePath};
+use crate::{
+    errs::{PrimitivesError, PrimitivesResult},
+    poseidon::Poseidon,
+};
+
+/// A sparse Poseidon Merkle tree of fixed depth, where absent leaves are zero.
+/// Only the nodes that differ from the root of an empty subtree are stored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseMerkleTree<F: PrimeField> {
+    arity: usize,
+    depth: usize,
+    hasher: Poseidon<F>,
+    /// The root of an empty subtree of every height, from the leaves up.
+    empty: Vec<F>,
+    /// The non-empty nodes, keyed by level and index in the level.
+    nodes: HashMap<(usize, u64), F>,
+}
+
+impl<F: PrimeField> SparseMerkleTree<F> {
+    /// Create an empty tree with `arity^depth` leaves, at most `2^64` of them.
+    pub fn new(arity: usize, depth: usize) -> PrimitivesResult<Self> {
+        let hasher = node_hasher(arity)?;
+        match (arity as u128).checked_pow(depth as u32) {
+            Some(num_leaves) if depth > 0 && num_leaves <= 1 << 64 => {}
+            _ => return Err(PrimitivesError::MerkleArityError),
+        }
+
+        let mut empty = vec![F::ZERO];
+        for level in 0..depth {
+            empty.push(hasher.hash(&vec![empty[level]; arity]));
+        }
+
+        Ok(Self {
+            arity,
+            depth,
+            hasher,
+            empty,
+            nodes: HashMap::new(),
+        })
+    }
+
+    /// Return the arity of the tree.
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
+    /// Return the number of levels above the leaves.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Return the root of the tree.
+    pub fn root(&self) -> F {
+        self.node(self.depth, 0)
+    }
+
+    /// Return the leaf at `index`, zero if it was never set.
+    pub fn get(&self, index: u64) -> PrimitivesResult<F> {
+        self.check_index(index)?;
+        Ok(self.node(0, index))
+    }
+
+    /// Set the leaf at `index` to `value`, updating the nodes above it.
+    pub fn insert(&mut self, index: u64, value: F) -> PrimitivesResult<()> {
+        self.check_index(index)?;
+        let arity = self.arity as u64;
+        let mut pos = index;
+        self.set_node(0, pos, value);
+        for level in 0..self.depth {
+            let first = pos - pos % arity;
+            let children: Vec<F> = (0..arity)
+                .map(|offset| self.node(level, first + offset))
+                .collect();
+            pos /= arity;
+            let node = self.hasher.hash(&children);
+            self.set_node(level + 1, pos, node);
+        }
+        Ok(())
+    }
+
+    /// Reset the leaf at `index` to zero.
+    pub fn remove(&mut self, index: u64) -> PrimitivesResult<()> {
+        self.insert(index, F::ZERO)
+    }
+
+    /// Return the authentication path of the leaf at `index`.
+    pub fn path(&self, index: u64) -> PrimitivesResult<MerklePath<F>> {
+        self.check_index(index)?;
+        let arity = self.arity as u64;
+        let mut pos = index;
+        let siblings = (0..self.depth)
+            .map(|level| {
+                // The last child is at most `u64::MAX`, `first + arity` may overflow.
+                let first = pos - pos % arity;
+                let siblings = (0..arity)
+                    .map(|offset| first + offset)
+                    .filter(|i| *i != pos)
+                    .map(|i| self.node(level, i))
+                    .collect();
+                pos /= arity;
+                siblings
+            })
+            .collect();
+        Ok(MerklePath { index, siblings })
+    }
+
+    fn check_index(&self, index: u64) -> PrimitivesResult<()> {
+        match (self.arity as u64).checked_pow(self.depth as u32) {
+            Some(num_leaves) if index >= num_leaves => Err(PrimitivesError::MerkleIndexError),
+            _ => Ok(()),
+        }
+    }
+
+    fn node(&self, level: usize, index: u64) -> F {
+        self.nodes
+            .get(&(level, index))
+            .copied()
+            .unwrap_or(self.empty[level])
+    }
+
+    fn set_node(&mut self, level: usize, index: u64, node: F) {
+        if node == self.empty[level] {
+            self.nodes.remove(&(level, index));
+        } else {
+            self.nodes.insert((level, index), node);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_sparse_merkle {
+    use super::*;
+    use crate::merkle::MerkleTree;
+    use ark_bn254::Fr;
+
+    #[test]
+    fn test_matches_dense_tree() {
+        let mut sparse = SparseMerkleTree::<Fr>::new(4, 3).unwrap();
+        let mut leaves = vec![Fr::from(0u64); 64];
+        assert_eq!(sparse.root(), MerkleTree::new(4, &leaves).unwrap().root());
+
+        for (i, index) in [0u64, 5, 17, 63].iter().enumerate() {
+            leaves[*index as usize] = Fr::from(i as u64 + 10);
+            sparse.insert(*index, leaves[*index as usize]).unwrap();
+        }
+        let dense = MerkleTree::new(4, &leaves).unwrap();
+        assert_eq!(sparse.root(), dense.root());
+        for index in [0u64, 1, 17, 40] {
+            assert_eq!(sparse.get(index).unwrap(), leaves[index as usize]);
+            assert_eq!(sparse.path(index).unwrap(), dense.path(index).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut tree = SparseMerkleTree::<Fr>::new(2, 64).unwrap();
+        let empty_root = tree.root();
+        let index = u64::MAX - 3;
+        let value = Fr::from(42u64);
+
+        tree.insert(index, value).unwrap();
+        assert_eq!(tree.get(index).unwrap(), value);
+        let path = tree.path(index).unwrap();
+        path.verify(tree.depth(), &tree.root(), &value).unwrap();
+        assert!(path
+            .verify(tree.depth(), &tree.root(), &Fr::from(0u64))
+            .is_err());
+
+        // Absence proof of another leaf.
+        tree.path(7)
+            .unwrap()
+            .verify(tree.depth(), &tree.root(), &Fr::from(0u64))
+            .unwrap();
+
+        tree.remove(index).unwrap();
+        assert_eq!(tree.root(), empty_root);
+        assert!(tree.nodes.is_empty());
+    }
+
+    #[test]
+    fn test_last_indices() {
+        for (arity, depth) in [(2, 64), (16, 16), (4, 32)] {
+            let mut tree = SparseMerkleTree::<Fr>::new(arity, depth).unwrap();
+            for (i, index) in [u64::MAX, u64::MAX - 1].into_iter().enumerate() {
+                let value = Fr::from(i as u64 + 1);
+                tree.insert(index, value).unwrap();
+                assert_eq!(tree.get(index).unwrap(), value);
+            }
+            for (i, index) in [u64::MAX, u64::MAX - 1].into_iter().enumerate() {
+                let path = tree.path(index).unwrap();
+                path.verify(tree.depth(), &tree.root(), &Fr::from(i as u64 + 1))
+                    .unwrap();
+            }
+            tree.remove(u64::MAX).unwrap();
+            tree.remove(u64::MAX - 1).unwrap();
+            assert!(tree.nodes.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        assert!(SparseMerkleTree::<Fr>::new(2, 65).is_err());
+        assert!(SparseMerkleTree::<Fr>::new(16, 0).is_err());
+        let mut tree = SparseMerkleTree::<Fr>::new(3, 2).unwrap();
+        assert!(tree.insert(9, Fr::from(1u64)).is_err());
+        assert!(tree.get(8).is_ok());
+    }
+}
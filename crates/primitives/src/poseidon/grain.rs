@@ -0,0 +1,83 @@
+use ark_ff::{BigInteger, PrimeField};
+
+/// The Grain LFSR used by the Poseidon reference implementation to derive
+/// round constants and MDS matrices from the instance parameters.
+#[derive(Debug, Clone)]
+pub(crate) struct GrainLFSR {
+    state: [bool; 80],
+    head: usize,
+}
+
+impl GrainLFSR {
+    /// Initialize the LFSR for a prime field instance with an `x^alpha` S-box.
+    pub(crate) fn new(field_size: u64, width: u64, full_rounds: u64, partial_rounds: u64) -> Self {
+        let mut state = [false; 80];
+        let mut pos = 0;
+        // field type (prime field) and S-box type (x^alpha)
+        for (value, bits) in [
+            (1, 2),
+            (0, 4),
+            (field_size, 12),
+            (width, 12),
+            (full_rounds, 10),
+            (partial_rounds, 10),
+        ] {
+            for i in (0..bits).rev() {
+                state[pos] = (value >> i) & 1 == 1;
+                pos += 1;
+            }
+        }
+        for bit in state[pos..].iter_mut() {
+            *bit = true;
+        }
+
+        let mut lfsr = GrainLFSR { state, head: 0 };
+        for _ in 0..160 {
+            lfsr.next_bit();
+        }
+        lfsr
+    }
+
+    /// Clock the register once and return the new bit.
+    fn next_bit(&mut self) -> bool {
+        let bit = |i: usize| self.state[(self.head + i) % 80];
+        let new_bit = bit(62) ^ bit(51) ^ bit(38) ^ bit(23) ^ bit(13) ^ bit(0);
+        self.state[self.head] = new_bit;
+        self.head = (self.head + 1) % 80;
+        new_bit
+    }
+
+    /// Return the next filtered output bit: bits are produced in pairs and the
+    /// second one is kept only when the first one is set.
+    fn next_filtered_bit(&mut self) -> bool {
+        loop {
+            let keep = self.next_bit();
+            let bit = self.next_bit();
+            if keep {
+                return bit;
+            }
+        }
+    }
+
+    /// Return the next `num_bits` filtered bits, most significant bit first.
+    fn next_bits(&mut self, num_bits: usize) -> Vec<bool> {
+        (0..num_bits).map(|_| self.next_filtered_bit()).collect()
+    }
+
+    /// Sample a field element by rejection, as done for round constants.
+    pub(crate) fn next_field_element<F: PrimeField>(&mut self) -> F {
+        loop {
+            let bits = self.next_bits(F::MODULUS_BIT_SIZE as usize);
+            if let Some(elem) = F::from_bigint(F::BigInt::from_bits_be(&bits)) {
+                return elem;
+            }
+        }
+    }
+
+    /// Sample a field element reducing modulo the field order, as done for the
+    /// MDS matrix.
+    pub(crate) fn next_field_element_mod_order<F: PrimeField>(&mut self) -> F {
+        let bits = self.next_bits(F::MODULUS_BIT_SIZE as usize);
+        F::from_be_bytes_mod_order(&F::BigInt::from_bits_be(&bits).to_bytes_be())
+    }
+}
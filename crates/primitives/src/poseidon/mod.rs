@@ -0,0 +1,281 @@
+mod grain;
+pub mod poseidon2;
+
+use ark_ff::PrimeField;
+
+use self::grain::GrainLFSR;
+
+/// Number of full rounds, shared by every supported width.
+pub const FULL_ROUNDS: usize = 8;
+
+/// Exponent of the S-box `x^alpha`.
+pub const ALPHA: u64 = 5;
+
+/// Number of partial rounds for the widths 2 to 17, targeting 128-bit
+/// security over ~255-bit prime fields with `x^5` S-boxes.
+const PARTIAL_ROUNDS: [usize; 16] = [
+    56, 57, 56, 60, 60, 63, 64, 63, 60, 66, 60, 65, 70, 60, 64, 68,
+];
+
+/// The parameters of a Poseidon permutation instance.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoseidonParameters<F: PrimeField> {
+    /// The state width `t`.
+    pub width: usize,
+    /// The number of full rounds.
+    pub full_rounds: usize,
+    /// The number of partial rounds.
+    pub partial_rounds: usize,
+    /// The round constants, `width` of them per round.
+    pub round_constants: Vec<Vec<F>>,
+    /// The MDS matrix, in row-major order.
+    pub mds: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> PoseidonParameters<F> {
+    /// Generate the standard parameters of width `width` (between 2 and 17).
+    /// Round constants and the Cauchy MDS matrix are derived with the Grain
+    /// LFSR exactly as in the Poseidon reference implementation, so the
+    /// instances over the BN254 and BLS12-381 scalar fields match the
+    /// reference test vectors.
+    pub fn new(width: usize) -> Self {
+        assert!(
+            (2..=17).contains(&width),
+            "Poseidon width must be between 2 and 17"
+        );
+        let full_rounds = FULL_ROUNDS;
+        let partial_rounds = PARTIAL_ROUNDS[width - 2];
+
+        let mut grain = GrainLFSR::new(
+            F::MODULUS_BIT_SIZE as u64,
+            width as u64,
+            full_rounds as u64,
+            partial_rounds as u64,
+        );
+        let round_constants = (0..full_rounds + partial_rounds)
+            .map(|_| (0..width).map(|_| grain.next_field_element()).collect())
+            .collect();
+        let mds = Self::cauchy_matrix(&mut grain, width);
+
+        Self {
+            width,
+            full_rounds,
+            partial_rounds,
+            round_constants,
+            mds,
+        }
+    }
+
+    /// Sample `2 * width` distinct elements `x_i, y_j` and build the matrix
+    /// `M[i][j] = 1 / (x_i + y_j)`.
+    fn cauchy_matrix(grain: &mut GrainLFSR, width: usize) -> Vec<Vec<F>> {
+        loop {
+            let mut elems: Vec<F> = (0..2 * width)
+                .map(|_| grain.next_field_element_mod_order())
+                .collect();
+            while (1..elems.len()).any(|i| elems[..i].contains(&elems[i])) {
+                elems = (0..2 * width)
+                    .map(|_| grain.next_field_element_mod_order())
+                    .collect();
+            }
+            let (xs, ys) = elems.split_at(width);
+            let mds: Option<Vec<Vec<F>>> = xs
+                .iter()
+                .map(|x| ys.iter().map(|y| (*x + y).inverse()).collect())
+                .collect();
+            if let Some(mds) = mds {
+                return mds;
+            }
+        }
+    }
+}
+
+/// The Poseidon permutation and the fixed-length sponge hash built on it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Poseidon<F: PrimeField> {
+    params: PoseidonParameters<F>,
+}
+
+impl<F: PrimeField> Poseidon<F> {
+    /// Create the standard instance of width `width`.
+    pub fn new(width: usize) -> Self {
+        Self::from_parameters(PoseidonParameters::new(width))
+    }
+
+    /// Create an instance from explicit parameters.
+    pub fn from_parameters(params: PoseidonParameters<F>) -> Self {
+        Self { params }
+    }
+
+    /// Return the parameters of this instance.
+    pub fn parameters(&self) -> &PoseidonParameters<F> {
+        &self.params
+    }
+
+    /// Return the state width.
+    pub fn width(&self) -> usize {
+        self.params.width
+    }
+
+    /// Return the sponge rate, i.e. the number of elements absorbed per permutation.
+    pub fn rate(&self) -> usize {
+        self.params.width - 1
+    }
+
+    /// Apply the permutation to `state` in place.
+    pub fn permute(&self, state: &mut [F]) {
+        assert_eq!(state.len(), self.params.width);
+        let half_full_rounds = self.params.full_rounds / 2;
+        let num_rounds = self.params.full_rounds + self.params.partial_rounds;
+        for (round, constants) in self.params.round_constants.iter().enumerate() {
+            for (elem, c) in state.iter_mut().zip(constants) {
+                *elem += c;
+            }
+            if round < half_full_rounds || round >= num_rounds - half_full_rounds {
+                for elem in state.iter_mut() {
+                    *elem = elem.pow([ALPHA]);
+                }
+            } else {
+                state[0] = state[0].pow([ALPHA]);
+            }
+            self.mix(state);
+        }
+    }
+
+    /// Multiply the state by the MDS matrix.
+    fn mix(&self, state: &mut [F]) {
+        let result: Vec<F> = self
+            .params
+            .mds
+            .iter()
+            .map(|row| row.iter().zip(state.iter()).map(|(m, s)| *m * s).sum())
+            .collect();
+        state.copy_from_slice(&result);
+    }
+
+    /// Hash a fixed-length sequence of field elements into one element.
+    ///
+    /// The first state element is the capacity, initialized with the
+    /// domain separator `len * 2^64` for fixed-length hashing; the inputs
+    /// are absorbed `rate` elements at a time into the rest of the state.
+    /// # Example
+    /// ```
+    /// use ark_bn254::Fr;
+    /// use primitives::poseidon::Poseidon;
+    ///
+    /// let poseidon = Poseidon::<Fr>::new(3);
+    /// let a = poseidon.hash(&[Fr::from(1u64), Fr::from(2u64)]);
+    /// let b = poseidon.hash(&[Fr::from(2u64), Fr::from(1u64)]);
+    /// assert_ne!(a, b);
+    /// ```
+    pub fn hash(&self, inputs: &[F]) -> F {
+        let mut state = vec![F::ZERO; self.params.width];
+        state[0] = F::from(inputs.len() as u64) * F::from(1u128 << 64);
+        if inputs.is_empty() {
+            self.permute(&mut state);
+        }
+        for chunk in inputs.chunks(self.rate()) {
+            for (elem, input) in state[1..].iter_mut().zip(chunk) {
+                *elem += input;
+            }
+            self.permute(&mut state);
+        }
+        state[1]
+    }
+
+    /// Hash exactly `rate` field elements as circomlib's `Poseidon(rate)`:
+    /// the capacity starts at zero and the output is the first element of
+    /// the state after a single permutation.
+    pub fn hash_circomlib(&self, inputs: &[F]) -> F {
+        assert_eq!(inputs.len(), self.rate());
+        let mut state = vec![F::ZERO; self.params.width];
+        state[1..].copy_from_slice(inputs);
+        self.permute(&mut state);
+        state[0]
+    }
+}
+
+#[cfg(test)]
+mod tests_poseidon {
+    use super::*;
+    use ark_ff::{BigInteger, MontFp};
+
+    fn check_permutation<F: PrimeField>(expected: [F; 3]) {
+        let poseidon = Poseidon::<F>::new(3);
+        let mut state = [F::from(0u64), F::from(1u64), F::from(2u64)];
+        poseidon.permute(&mut state);
+        assert_eq!(state, expected);
+    }
+
+    #[test]
+    fn test_permutation_bn254() {
+        // poseidonperm_x5_254_3 from the reference implementation
+        check_permutation::<ark_bn254::Fr>([
+            MontFp!("7853200120776062878684798364095072458815029376092732009249414926327459813530"),
+            MontFp!("7142104613055408817911962100316808866448378443474503659992478482890339429929"),
+            MontFp!("6549537674122432311777789598043107870002137484850126429160507761192163713804"),
+        ]);
+    }
+
+    #[test]
+    fn test_permutation_bls12_381() {
+        // poseidonperm_x5_255_3 from the reference implementation
+        check_permutation::<ark_bls12_381::Fr>([
+            MontFp!(
+                "18456658763349757341014058622209659766100673761449600566550821987295786346378"
+            ),
+            MontFp!(
+                "37068251774887509885063625701815026138353041152735229476479055620962268601796"
+            ),
+            MontFp!(
+                "26763157702141528937904191329664859174584798817251788852101947537759678822298"
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_parameters() {
+        for width in 2..=17 {
+            let params = PoseidonParameters::<ark_bn254::Fr>::new(width);
+            assert_eq!(params.round_constants.len(), 8 + PARTIAL_ROUNDS[width - 2]);
+            assert!(params.round_constants.iter().all(|c| c.len() == width));
+            assert_eq!(params.mds.len(), width);
+            for c in params.round_constants.iter().flatten() {
+                assert!(c.into_bigint().num_bits() <= ark_bn254::Fr::MODULUS_BIT_SIZE);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hash_circomlib() {
+        // poseidon([1, 2]) and poseidon([1, 2, 3, 4]) from circomlibjs
+        let inputs: Vec<ark_bn254::Fr> = (1..=4u64).map(ark_bn254::Fr::from).collect();
+        assert_eq!(
+            Poseidon::new(3).hash_circomlib(&inputs[..2]),
+            MontFp!("7853200120776062878684798364095072458815029376092732009249414926327459813530")
+        );
+        assert_eq!(
+            Poseidon::new(5).hash_circomlib(&inputs),
+            MontFp!(
+                "18821383157269793795438455681495246036402687001665670618754263018637548127333"
+            )
+        );
+    }
+
+    #[test]
+    fn test_hash() {
+        let poseidon = Poseidon::<ark_bn254::Fr>::new(3);
+        let inputs: Vec<ark_bn254::Fr> = (0..5u64).map(ark_bn254::Fr::from).collect();
+
+        // The hash depends on every input and on the input length.
+        let h = poseidon.hash(&inputs);
+        assert_eq!(h, poseidon.hash(&inputs));
+        assert_ne!(h, poseidon.hash(&inputs[..4]));
+        let mut tweaked = inputs.clone();
+        tweaked[4] += ark_bn254::Fr::from(1u64);
+        assert_ne!(h, poseidon.hash(&tweaked));
+        let mut padded = inputs.clone();
+        padded.push(ark_bn254::Fr::from(0u64));
+        assert_ne!(h, poseidon.hash(&padded));
+    }
+}
@@ -0,0 +1,154 @@
+use ark_ff::PrimeField;
+
+use super::{grain::GrainLFSR, ALPHA, FULL_ROUNDS};
+
+/// State width of the supported Poseidon2 instance.
+pub const WIDTH: usize = 3;
+
+/// Number of partial rounds of the width-3 instance.
+pub const PARTIAL_ROUNDS: usize = 56;
+
+/// The Poseidon2 permutation of width 3.
+///
+/// The external layer is `circ(2, 1, 1)`, the internal layer is
+/// `1 + diag(1, 1, 2)`, and the round constants are derived with the Grain
+/// LFSR (`width` constants per full round, one per partial round), which is
+/// the reference instance over the BN254 scalar field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Poseidon2<F: PrimeField> {
+    /// Round constants of the first half of the full rounds.
+    pub initial_round_constants: Vec<[F; WIDTH]>,
+    /// Round constants of the partial rounds, applied to the first element.
+    pub partial_round_constants: Vec<F>,
+    /// Round constants of the second half of the full rounds.
+    pub terminal_round_constants: Vec<[F; WIDTH]>,
+}
+
+impl<F: PrimeField> Default for Poseidon2<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: PrimeField> Poseidon2<F> {
+    /// Generate the round constants of the width-3 instance.
+    pub fn new() -> Self {
+        let mut grain = GrainLFSR::new(
+            F::MODULUS_BIT_SIZE as u64,
+            WIDTH as u64,
+            FULL_ROUNDS as u64,
+            PARTIAL_ROUNDS as u64,
+        );
+        let initial_round_constants = (0..FULL_ROUNDS / 2)
+            .map(|_| Self::full_round_constants(&mut grain))
+            .collect();
+        let partial_round_constants = (0..PARTIAL_ROUNDS)
+            .map(|_| grain.next_field_element())
+            .collect();
+        let terminal_round_constants = (0..FULL_ROUNDS / 2)
+            .map(|_| Self::full_round_constants(&mut grain))
+            .collect();
+
+        Self {
+            initial_round_constants,
+            partial_round_constants,
+            terminal_round_constants,
+        }
+    }
+
+    fn full_round_constants(grain: &mut GrainLFSR) -> [F; WIDTH] {
+        [
+            grain.next_field_element(),
+            grain.next_field_element(),
+            grain.next_field_element(),
+        ]
+    }
+
+    /// Apply the permutation to `state` in place.
+    pub fn permute(&self, state: &mut [F; WIDTH]) {
+        Self::external_layer(state);
+        for constants in self.initial_round_constants.iter() {
+            Self::full_round(state, constants);
+        }
+        for constant in self.partial_round_constants.iter() {
+            state[0] += constant;
+            state[0] = state[0].pow([ALPHA]);
+            Self::internal_layer(state);
+        }
+        for constants in self.terminal_round_constants.iter() {
+            Self::full_round(state, constants);
+        }
+    }
+
+    /// Hash two field elements into one, e.g. to compress a Merkle node.
+    /// The output is the first element of the permuted state `[a, b, 0]`
+    /// added to `a` (feed-forward), as in the reference compression mode.
+    pub fn compress(&self, a: &F, b: &F) -> F {
+        let mut state = [*a, *b, F::ZERO];
+        self.permute(&mut state);
+        state[0] + a
+    }
+
+    fn full_round(state: &mut [F; WIDTH], constants: &[F; WIDTH]) {
+        for (elem, c) in state.iter_mut().zip(constants) {
+            *elem += c;
+            *elem = elem.pow([ALPHA]);
+        }
+        Self::external_layer(state);
+    }
+
+    /// Multiply by `circ(2, 1, 1)`.
+    fn external_layer(state: &mut [F; WIDTH]) {
+        let sum: F = state.iter().sum();
+        for elem in state.iter_mut() {
+            *elem += sum;
+        }
+    }
+
+    /// Multiply by `1 + diag(1, 1, 2)`.
+    fn internal_layer(state: &mut [F; WIDTH]) {
+        let sum: F = state.iter().sum();
+        state[0] += sum;
+        state[1] += sum;
+        state[2].double_in_place();
+        state[2] += sum;
+    }
+}
+
+#[cfg(test)]
+mod tests_poseidon2 {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_ff::MontFp;
+
+    #[test]
+    fn test_permutation_bn254() {
+        let poseidon2 = Poseidon2::<Fr>::new();
+        let mut state = [Fr::from(0u64), Fr::from(1u64), Fr::from(2u64)];
+        poseidon2.permute(&mut state);
+        // Test vector of the reference BN254 width-3 instance
+        assert_eq!(
+            state,
+            [
+                MontFp!(
+                    "5297208644449048816064511434384511824916970985131888684874823260532015509555"
+                ),
+                MontFp!(
+                    "21816030159894113985964609355246484851575571273661473159848781012394295965040"
+                ),
+                MontFp!(
+                    "13940986381491601233448981668101586453321811870310341844570924906201623195336"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compress() {
+        let poseidon2 = Poseidon2::<Fr>::new();
+        let a = Fr::from(1u64);
+        let b = Fr::from(2u64);
+        assert_eq!(poseidon2.compress(&a, &b), poseidon2.compress(&a, &b));
+        assert_ne!(poseidon2.compress(&a, &b), poseidon2.compress(&b, &a));
+    }
+}